@@ -14,6 +14,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
 
 /// Safety limits (matching TypeScript constants)
 pub const MAX_SESSION_DURATION_MS: u64 = 15 * 60 * 1000; // 15 minutes
@@ -22,6 +23,22 @@ pub const MAX_DUTY_CYCLE: f32 = 0.7;
 pub const COOLDOWN_PERIOD_MS: u64 = 5 * 60 * 1000; // 5 minutes
 pub const SAMPLE_RATE: u32 = 44100;
 
+/// Number of mic-input samples analyzed per envelope/pitch update
+const ANALYSIS_WINDOW: usize = 1024;
+/// Capacity of the mic-input staging ring (must exceed `ANALYSIS_WINDOW`)
+const INPUT_RING_CAPACITY: usize = 4096;
+/// Smoothing factor for the biofeedback envelope (closer to 1.0 = slower)
+const ENVELOPE_SMOOTHING: f32 = 0.9;
+/// How often the device-watcher thread re-checks device presence
+const DEVICE_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+/// How many consecutive times the device-watcher will retry rebuilding a
+/// dead stream before giving up and waiting for the device to reappear on
+/// its own, so a permanently unplugged interface doesn't retry forever.
+const MAX_RECOVERY_RETRIES: u32 = 5;
+/// Base backoff delay between rebuild attempts, doubled after each failure
+/// (on top of the regular `DEVICE_WATCH_INTERVAL` poll) up to `MAX_RECOVERY_RETRIES`.
+const RECOVERY_BACKOFF_BASE_MS: u64 = 500;
+
 /// Waveform types
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -52,6 +69,34 @@ impl std::str::FromStr for WaveformType {
     }
 }
 
+/// Which audio parameter, if any, the microphone envelope should drive
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BiofeedbackMode {
+    Off,
+    Amplitude,
+    Frequency,
+}
+
+impl Default for BiofeedbackMode {
+    fn default() -> Self {
+        BiofeedbackMode::Off
+    }
+}
+
+impl std::str::FromStr for BiofeedbackMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(BiofeedbackMode::Off),
+            "amplitude" => Ok(BiofeedbackMode::Amplitude),
+            "frequency" => Ok(BiofeedbackMode::Frequency),
+            _ => Err(format!("Invalid biofeedback mode: {}", s)),
+        }
+    }
+}
+
 /// Frequency preset configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrequencyPreset {
@@ -85,20 +130,45 @@ pub struct AudioDeviceInfo {
     pub channels: u16,
 }
 
+/// Whether the output stream negotiates shared access (mixed with other
+/// applications) or exclusive access (device reserved entirely for us, the
+/// only way to honor an exact low-latency buffer size on WASAPI/ASIO).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareMode {
+    Shared,
+    Exclusive,
+}
+
+impl Default for ShareMode {
+    fn default() -> Self {
+        ShareMode::Shared
+    }
+}
+
 /// Audio configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfig {
     pub device_id: Option<String>,
+    /// Backend to resolve the device against (e.g. "ASIO", "JACK",
+    /// "WASAPI"), as reported by `get_audio_hosts`. Only consulted when
+    /// `device_id` is unset and `share_mode` isn't `Exclusive` (exclusive
+    /// mode already picks its own backend via `preferred_exclusive_host`);
+    /// `None` falls back to `cpal::default_host()`.
+    pub host_id: Option<String>,
     pub sample_rate: u32,
     pub buffer_size: u32,
+    pub share_mode: ShareMode,
 }
 
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
             device_id: None, // Use system default
+            host_id: None,   // Use system default host
             sample_rate: 44100,
             buffer_size: 512,
+            share_mode: ShareMode::Shared,
         }
     }
 }
@@ -136,6 +206,25 @@ pub struct AudioParams {
     pub amplitude: AtomicU64,       // f32 bits stored as u64
     pub waveform: Mutex<WaveformType>,
     pub is_playing: AtomicBool,
+    /// Authoritative left-channel (carrier) oscillator phase, in 0.0..1.0,
+    /// stored as f32 bits. Advanced by exactly one "clock" stream (see
+    /// `build_output_stream_for_format`) so every stream sharing these
+    /// params - e.g. the members of an aggregate output set up via
+    /// `set_aggregate_devices` - stays phase-locked instead of drifting
+    /// against its own local accumulator.
+    pub phase: AtomicU64,
+    /// Right-channel phase for binaural beats, run at `frequency_hz +
+    /// beat_offset_hz` independently of `phase`'s carrier rate. Zero
+    /// `beat_offset_hz` (the default) just keeps it identical to `phase`.
+    pub beat_phase: AtomicU64,
+    /// Per-ear frequency offset for binaural beats, in Hz. 0.0 disables
+    /// the binaural effect (both channels play the carrier frequency).
+    pub beat_offset_hz: AtomicU64,
+    /// Isochronic pulse phase, run at `isochronic_rate_hz`, independently
+    /// gating both channels' amplitude on/off.
+    pub isochronic_phase: AtomicU64,
+    /// Isochronic pulse rate, in Hz. 0.0 (the default) disables pulsing.
+    pub isochronic_rate_hz: AtomicU64,
 }
 
 impl AudioParams {
@@ -145,6 +234,11 @@ impl AudioParams {
             amplitude: AtomicU64::new(0.5_f32.to_bits() as u64),
             waveform: Mutex::new(WaveformType::Sine),
             is_playing: AtomicBool::new(false),
+            phase: AtomicU64::new(0.0_f32.to_bits() as u64),
+            beat_phase: AtomicU64::new(0.0_f32.to_bits() as u64),
+            beat_offset_hz: AtomicU64::new(0.0_f32.to_bits() as u64),
+            isochronic_phase: AtomicU64::new(0.0_f32.to_bits() as u64),
+            isochronic_rate_hz: AtomicU64::new(0.0_f32.to_bits() as u64),
         }
     }
 
@@ -163,16 +257,196 @@ impl AudioParams {
     pub fn set_amplitude(&self, amp: f32) {
         self.amplitude.store(amp.to_bits() as u64, Ordering::Relaxed);
     }
+
+    pub fn get_phase(&self) -> f32 {
+        f32::from_bits(self.phase.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_phase(&self, phase: f32) {
+        self.phase.store(phase.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_beat_phase(&self) -> f32 {
+        f32::from_bits(self.beat_phase.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_beat_phase(&self, phase: f32) {
+        self.beat_phase.store(phase.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_beat_offset(&self) -> f32 {
+        f32::from_bits(self.beat_offset_hz.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_beat_offset(&self, hz: f32) {
+        self.beat_offset_hz.store(hz.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_isochronic_phase(&self) -> f32 {
+        f32::from_bits(self.isochronic_phase.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_isochronic_phase(&self, phase: f32) {
+        self.isochronic_phase.store(phase.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_isochronic_rate(&self) -> f32 {
+        f32::from_bits(self.isochronic_rate_hz.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_isochronic_rate(&self, hz: f32) {
+        self.isochronic_rate_hz.store(hz.to_bits() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Microphone-derived biofeedback parameters shared with the input thread
+#[derive(Debug)]
+pub struct BiofeedbackParams {
+    pub audio_level: AtomicU64,     // f32 bits, smoothed RMS envelope
+    pub peak_level: AtomicU64,      // f32 bits, peak |sample| over the analysis window
+    pub dominant_freq_hz: AtomicU64, // f32 bits, zero-crossing estimate
+    pub mode: Mutex<BiofeedbackMode>,
+    pub frequency_range_hz: Mutex<(f32, f32)>,
+}
+
+impl BiofeedbackParams {
+    pub fn new() -> Self {
+        Self {
+            audio_level: AtomicU64::new(0.0_f32.to_bits() as u64),
+            peak_level: AtomicU64::new(0.0_f32.to_bits() as u64),
+            dominant_freq_hz: AtomicU64::new(0.0_f32.to_bits() as u64),
+            mode: Mutex::new(BiofeedbackMode::Off),
+            frequency_range_hz: Mutex::new((1.0, 60.0)),
+        }
+    }
+
+    pub fn get_audio_level(&self) -> f32 {
+        f32::from_bits(self.audio_level.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_audio_level(&self, level: f32) {
+        self.audio_level.store(level.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_peak_level(&self) -> f32 {
+        f32::from_bits(self.peak_level.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_peak_level(&self, level: f32) {
+        self.peak_level.store(level.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_dominant_freq(&self) -> f32 {
+        f32::from_bits(self.dominant_freq_hz.load(Ordering::Relaxed) as u32)
+    }
+
+    pub fn set_dominant_freq(&self, freq: f32) {
+        self.dominant_freq_hz.store(freq.to_bits() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Microphone-derived parameters, as read back by the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputMetrics {
+    pub audio_level: f32,
+    pub dominant_freq_hz: f32,
+}
+
+/// Whether the currently configured output device is present right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceStatus {
+    pub device_id: Option<String>,
+    pub is_present: bool,
+}
+
+/// Lock-free single-producer/single-consumer ring staging raw mic samples
+/// between the input callback and the analysis done in that same callback.
+/// Samples older than `INPUT_RING_CAPACITY` are silently overwritten.
+struct InputRing {
+    slots: Vec<AtomicU64>, // f32 bits, one per sample
+    write_cursor: AtomicU64,
+}
+
+impl InputRing {
+    fn new() -> Self {
+        Self {
+            slots: (0..INPUT_RING_CAPACITY).map(|_| AtomicU64::new(0)).collect(),
+            write_cursor: AtomicU64::new(0),
+        }
+    }
+
+    fn push(&self, sample: f32) {
+        let index = self.write_cursor.fetch_add(1, Ordering::Relaxed);
+        self.slots[index as usize % INPUT_RING_CAPACITY]
+            .store(sample.to_bits() as u64, Ordering::Relaxed);
+    }
+
+    /// The most recent `count` samples pushed, oldest first. Shorter than
+    /// `count` until the ring has been filled at least that far.
+    fn latest(&self, count: usize) -> Vec<f32> {
+        let written = self.write_cursor.load(Ordering::Relaxed);
+        let available = written.min(count as u64) as usize;
+        let start = written as usize - available;
+        (start..written as usize)
+            .map(|i| f32::from_bits(self.slots[i % INPUT_RING_CAPACITY].load(Ordering::Relaxed) as u32))
+            .collect()
+    }
+}
+
+/// RMS level of a sample window, in 0.0..=1.0
+fn rms_level(window: &[f32]) -> f32 {
+    if window.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f32 = window.iter().map(|s| s * s).sum();
+    (sum_sq / window.len() as f32).sqrt()
+}
+
+/// Dominant frequency estimate from the zero-crossing rate of a sample
+/// window. Coarse, but cheap enough to run every analysis window. The
+/// smallest nonzero estimate is one crossing per window, i.e.
+/// `sample_rate / (2 * window.len())` (about 21.5 Hz for an
+/// `ANALYSIS_WINDOW` at 44.1 kHz); slower signals read as 0.0.
+fn zero_crossing_frequency(window: &[f32], sample_rate: f32) -> f32 {
+    if window.len() < 2 {
+        return 0.0;
+    }
+    let crossings = window
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+    (crossings as f32 / 2.0) * sample_rate / window.len() as f32
 }
 
 /// Application state
 pub struct AppState {
     pub session: Arc<Mutex<SessionState>>,
     pub audio_params: Arc<AudioParams>,
-    pub audio_stream: Mutex<Option<Stream>>,
+    pub audio_stream: Arc<Mutex<Option<Stream>>>,
     pub audio_config: Arc<Mutex<AudioConfig>>,
     pub timer_handle: Mutex<Option<thread::JoinHandle<()>>>,
     pub timer_running: Arc<AtomicBool>,
+    pub biofeedback: Arc<BiofeedbackParams>,
+    pub input_stream: Mutex<Option<Stream>>,
+    pub input_ring: Arc<InputRing>,
+    pub device_lost: Arc<AtomicBool>,
+    pub device_watcher_running: Arc<AtomicBool>,
+    pub stream_latency_ms: Arc<AtomicU64>,
+    /// Streams opened by `set_aggregate_devices` for synchronized
+    /// multi-transducer output. Empty when aggregate output isn't in use.
+    pub aggregate_streams: Mutex<Vec<Stream>>,
+    /// Whether `start_monitoring` is actively emitting `coherence-level`
+    /// events from the input stream. Independent of `biofeedback.mode`
+    /// since either consumer can keep `input_stream` alive.
+    pub monitoring_running: Arc<AtomicBool>,
+    /// Whether monitoring should also nudge `AudioParams::frequency` from
+    /// the measured envelope (closed-loop entrainment), set by
+    /// `start_monitoring`'s `closed_loop` argument.
+    pub monitoring_closed_loop: Arc<AtomicBool>,
+    /// The WASAPI loopback capture stream opened by `start_loopback_capture`,
+    /// if any. Independent of `input_stream` - loopback reads back the
+    /// system's output mix rather than a microphone.
+    pub loopback_stream: Mutex<Option<Stream>>,
 }
 
 impl Default for AppState {
@@ -180,40 +454,289 @@ impl Default for AppState {
         Self {
             session: Arc::new(Mutex::new(SessionState::default())),
             audio_params: Arc::new(AudioParams::new()),
-            audio_stream: Mutex::new(None),
+            audio_stream: Arc::new(Mutex::new(None)),
             audio_config: Arc::new(Mutex::new(AudioConfig::default())),
             timer_handle: Mutex::new(None),
             timer_running: Arc::new(AtomicBool::new(false)),
+            biofeedback: Arc::new(BiofeedbackParams::new()),
+            input_stream: Mutex::new(None),
+            input_ring: Arc::new(InputRing::new()),
+            device_lost: Arc::new(AtomicBool::new(false)),
+            device_watcher_running: Arc::new(AtomicBool::new(false)),
+            stream_latency_ms: Arc::new(AtomicU64::new(0.0_f32.to_bits() as u64)),
+            aggregate_streams: Mutex::new(Vec::new()),
+            monitoring_running: Arc::new(AtomicBool::new(false)),
+            monitoring_closed_loop: Arc::new(AtomicBool::new(false)),
+            loopback_stream: Mutex::new(None),
         }
     }
 }
 
-/// Generate waveform sample
-fn generate_sample(waveform: WaveformType, phase: f32, amplitude: f32) -> f32 {
+/// PolyBLEP (polynomial band-limited step) correction for the discontinuity
+/// a naive square/sawtooth wave passes through at normalized phase `t`,
+/// given the per-sample phase increment `dt`. Adding/subtracting this near
+/// the jump rounds it off across roughly one sample's worth of phase -
+/// just enough to suppress the aliasing a hard discontinuity would
+/// otherwise fold back into the audible range. Also used to derive the
+/// band-limited triangle (see `TRIANGLE_INTEGRATOR` below), so sine is the
+/// only waveform still generated from its naive closed form.
+fn poly_blep(t: f32, dt: f32) -> f32 {
+    if t < dt {
+        let x = t / dt;
+        x + x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + x + x + 1.0
+    } else {
+        0.0
+    }
+}
+
+thread_local! {
+    /// Leaky-integrator state for deriving a band-limited triangle wave
+    /// from the PolyBLEP-corrected square wave (a triangle is the integral
+    /// of a square wave). Indexed by channel (0 = left, 1 = right) so the
+    /// independently-phased binaural channels each integrate their own
+    /// triangle instead of interleaving into one shared accumulator. Local
+    /// to whichever thread is running the stream's audio callback, same as
+    /// the rest of this file's per-callback state.
+    static TRIANGLE_INTEGRATOR: [std::cell::Cell<f32>; 2] =
+        [std::cell::Cell::new(0.0), std::cell::Cell::new(0.0)];
+}
+
+/// Generate waveform sample for `channel` (0 = left, 1 = right - see
+/// `TRIANGLE_INTEGRATOR`). Square and sawtooth are PolyBLEP-corrected so
+/// their discontinuities don't alias at the top of the 1-60 Hz range; the
+/// triangle is derived by leaky-integrating the band-limited square.
+/// `phase_increment` is the fraction of a cycle advanced per sample
+/// (`frequency / sample_rate`), needed by `poly_blep` to size its correction.
+fn generate_sample(
+    waveform: WaveformType,
+    phase: f32,
+    amplitude: f32,
+    phase_increment: f32,
+    channel: usize,
+) -> f32 {
     let safe_amplitude = amplitude.min(MAX_AMPLITUDE);
     match waveform {
         WaveformType::Sine => safe_amplitude * (2.0 * std::f32::consts::PI * phase).sin(),
         WaveformType::Square => {
-            if phase < 0.5 {
-                safe_amplitude
-            } else {
-                -safe_amplitude
-            }
+            let mut value = if phase < 0.5 { 1.0 } else { -1.0 };
+            value += poly_blep(phase, phase_increment);
+            value -= poly_blep((phase + 0.5).fract(), phase_increment);
+            safe_amplitude * value
         }
         WaveformType::Triangle => {
-            if phase < 0.5 {
-                safe_amplitude * (4.0 * phase - 1.0)
-            } else {
-                safe_amplitude * (3.0 - 4.0 * phase)
+            let mut square = if phase < 0.5 { 1.0 } else { -1.0 };
+            square += poly_blep(phase, phase_increment);
+            square -= poly_blep((phase + 0.5).fract(), phase_increment);
+
+            TRIANGLE_INTEGRATOR.with(|integrators| {
+                let integrator = &integrators[channel];
+                // A small leak keeps the integrator from drifting off with
+                // DC bias; scaling the leak by phase_increment keeps its
+                // time constant at a fixed two cycles, and 4 * phase_increment
+                // normalizes the slope, so a unit-amplitude triangle swings
+                // through (within about 1% of) its full -1.0..1.0 range once
+                // per cycle at any frequency.
+                let leak = 1.0 - 0.5 * phase_increment;
+                let value = integrator.get() * leak + square * 4.0 * phase_increment;
+                integrator.set(value);
+                safe_amplitude * value.clamp(-1.0, 1.0)
+            })
+        }
+        WaveformType::Sawtooth => {
+            let mut value = 2.0 * phase - 1.0;
+            value -= poly_blep(phase, phase_increment);
+            safe_amplitude * value
+        }
+    }
+}
+
+/// Build an output stream against `device`/`supported_config`, branching on
+/// the device's native sample format (F32/I16/U16) so ASIO/WASAPI exclusive-
+/// mode interfaces that refuse F32 still work. Each format's callback shares
+/// the same `AudioParams` read via `next_frame`, so they stay perfectly in
+/// sync regardless of which one is actually wired up. `next_frame` produces
+/// an independent (left, right) sample pair each frame - the right channel
+/// runs at `frequency_hz + beat_offset_hz` for binaural beats - which the
+/// format's loop averages into a mono frame, or writes to the first two
+/// channels with the left sample mirrored into any extra channels.
+///
+/// Phase is authoritative on `params.phase`/`params.beat_phase` rather than
+/// local accumulators, so multiple streams built against the same
+/// `AudioParams` (see `set_aggregate_devices`) stay phase-locked. Exactly
+/// one of them should be built with `is_clock: true` - that one advances
+/// the shared phases each sample; the rest pass `is_clock: false` and only
+/// read them, so a set of independently-scheduled device callbacks doesn't
+/// advance phase multiple times per sample period.
+///
+/// `sample_rate_override`, when given, is used for the phase increment
+/// instead of the rate reported by `supported_config` (needed when the
+/// caller has already picked a specific rate via [`AudioConfig`]).
+///
+/// `device_lost` is set by the stream's error callback whenever cpal reports
+/// `DeviceNotAvailable`, so a watcher thread can detect a mid-session
+/// unplug (see `spawn_device_watcher`) without polling the error itself.
+fn build_output_stream_for_format(
+    device: &Device,
+    supported_config: cpal::SupportedStreamConfig,
+    params: Arc<AudioParams>,
+    sample_rate_override: Option<f32>,
+    device_lost: Arc<AtomicBool>,
+    buffer_size_override: Option<u32>,
+    is_clock: bool,
+) -> Result<Stream, String> {
+    let sample_format = supported_config.sample_format();
+    let mut stream_config: StreamConfig = supported_config.into();
+    if let Some(size) = buffer_size_override {
+        stream_config.buffer_size = cpal::BufferSize::Fixed(size);
+    }
+    let sample_rate = sample_rate_override.unwrap_or(stream_config.sample_rate.0 as f32);
+    let channels = stream_config.channels as usize;
+
+    // Produces one (left, right) sample pair per frame. The right channel
+    // runs its own phase at `frequency + beat_offset_hz` for binaural
+    // beats; when `beat_offset_hz` is 0.0 (the default) it stays in lockstep
+    // with the left channel, reproducing the old mono-everywhere behavior.
+    // An optional isochronic pulse gates both channels' amplitude together.
+    let mut next_frame = move || -> (f32, f32) {
+        let frequency = params.get_frequency();
+        let beat_offset = params.get_beat_offset();
+        let amplitude = params.get_amplitude();
+        let waveform = *params.waveform.lock().unwrap();
+        let is_playing = params.is_playing.load(Ordering::Relaxed);
+        let isochronic_rate = params.get_isochronic_rate();
+
+        let left_phase = params.get_phase();
+        let right_phase = params.get_beat_phase();
+        let iso_phase = params.get_isochronic_phase();
+
+        let left_increment = frequency / sample_rate;
+        let right_increment = (frequency + beat_offset) / sample_rate;
+
+        let (mut left, mut right) = if is_playing {
+            (
+                generate_sample(waveform, left_phase, amplitude, left_increment, 0),
+                generate_sample(waveform, right_phase, amplitude, right_increment, 1),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        if is_playing && isochronic_rate > 0.0 {
+            let gate = if iso_phase < MAX_DUTY_CYCLE { 1.0 } else { 0.0 };
+            left *= gate;
+            right *= gate;
+        }
+
+        if is_clock {
+            let mut new_left_phase = left_phase + left_increment;
+            if new_left_phase >= 1.0 {
+                new_left_phase -= 1.0;
+            }
+            params.set_phase(new_left_phase);
+
+            let mut new_right_phase = right_phase + right_increment;
+            if new_right_phase >= 1.0 {
+                new_right_phase -= 1.0;
+            }
+            params.set_beat_phase(new_right_phase);
+
+            if isochronic_rate > 0.0 {
+                let mut new_iso_phase = iso_phase + isochronic_rate / sample_rate;
+                if new_iso_phase >= 1.0 {
+                    new_iso_phase -= 1.0;
+                }
+                params.set_isochronic_phase(new_iso_phase);
             }
         }
-        WaveformType::Sawtooth => safe_amplitude * (2.0 * phase - 1.0),
+
+        (left, right)
+    };
+
+    let err_fn = move |err: cpal::StreamError| {
+        if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+            device_lost.store(true, Ordering::Relaxed);
+        }
+        eprintln!("Audio stream error: {}", err);
+    };
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let (left, right) = next_frame();
+                    match frame.len() {
+                        0 => {}
+                        1 => frame[0] = (left + right) * 0.5,
+                        _ => {
+                            frame[0] = left;
+                            frame[1] = right;
+                            for extra in frame[2..].iter_mut() {
+                                *extra = left;
+                            }
+                        }
+                    }
+                }
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let (left, right) = next_frame();
+                    match frame.len() {
+                        0 => {}
+                        1 => frame[0] = ((left + right) * 0.5 * i16::MAX as f32) as i16,
+                        _ => {
+                            frame[0] = (left * i16::MAX as f32) as i16;
+                            frame[1] = (right * i16::MAX as f32) as i16;
+                            for extra in frame[2..].iter_mut() {
+                                *extra = (left * i16::MAX as f32) as i16;
+                            }
+                        }
+                    }
+                }
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [u16], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let (left, right) = next_frame();
+                    match frame.len() {
+                        0 => {}
+                        1 => frame[0] = (((left + right) * 0.5 * 0.5 + 0.5) * u16::MAX as f32) as u16,
+                        _ => {
+                            frame[0] = ((left * 0.5 + 0.5) * u16::MAX as f32) as u16;
+                            frame[1] = ((right * 0.5 + 0.5) * u16::MAX as f32) as u16;
+                            for extra in frame[2..].iter_mut() {
+                                *extra = ((left * 0.5 + 0.5) * u16::MAX as f32) as u16;
+                            }
+                        }
+                    }
+                }
+            },
+            err_fn,
+            None,
+        ),
+        other => return Err(format!("Unsupported sample format: {:?}", other)),
     }
+    .map_err(|e| format!("Failed to build output stream: {}", e))?;
+
+    Ok(stream)
 }
 
 /// Create audio stream for frequency output
 fn create_audio_stream(
     params: Arc<AudioParams>,
+    device_lost: Arc<AtomicBool>,
 ) -> Result<Stream, String> {
     let host = cpal::default_host();
     let device = host
@@ -224,43 +747,156 @@ fn create_audio_stream(
         .default_output_config()
         .map_err(|e| format!("Failed to get default output config: {}", e))?;
 
+    build_output_stream_for_format(&device, config, params, None, device_lost, None, true)
+}
+
+/// Create the microphone input stream shared by `start_biofeedback` and
+/// `start_monitoring`. Analyzes each callback's samples for peak/RMS level
+/// and dominant frequency, publishes them to `biofeedback`, and:
+/// - when opted in via `biofeedback.mode`, nudges `params`' amplitude or
+///   frequency to entrain to the measured envelope;
+/// - while `monitoring_running` is set, emits a `coherence-level` event
+///   with the same metrics for the frontend to chart in real time, and
+///   (when `monitoring_closed_loop` is also set) independently nudges
+///   frequency the same way `BiofeedbackMode::Frequency` does.
+fn create_input_stream(
+    app_handle: tauri::AppHandle,
+    params: Arc<AudioParams>,
+    biofeedback: Arc<BiofeedbackParams>,
+    ring: Arc<InputRing>,
+    monitoring_running: Arc<AtomicBool>,
+    monitoring_closed_loop: Arc<AtomicBool>,
+) -> Result<Stream, String> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or("No audio input device found")?;
+
+    let config = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to get default input config: {}", e))?;
+
     let sample_rate = config.sample_rate().0 as f32;
     let channels = config.channels() as usize;
 
-    let mut phase: f32 = 0.0;
+    let mut smoothed_level: f32 = 0.0;
 
     let stream = device
-        .build_output_stream(
+        .build_input_stream(
             &config.into(),
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                let frequency = params.get_frequency();
-                let amplitude = params.get_amplitude();
-                let waveform = *params.waveform.lock().unwrap();
-                let is_playing = params.is_playing.load(Ordering::Relaxed);
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                for frame in data.chunks(channels) {
+                    ring.push(frame[0]);
+                }
 
-                let phase_increment = frequency / sample_rate;
+                let window = ring.latest(ANALYSIS_WINDOW);
+                if window.len() < ANALYSIS_WINDOW / 2 {
+                    return;
+                }
 
-                for frame in data.chunks_mut(channels) {
-                    let sample = if is_playing {
-                        generate_sample(waveform, phase, amplitude)
-                    } else {
-                        0.0
-                    };
-
-                    for channel in frame.iter_mut() {
-                        *channel = sample;
+                let level = rms_level(&window);
+                let peak = window.iter().fold(0.0f32, |acc, s| acc.max(s.abs()));
+                let freq = zero_crossing_frequency(&window, sample_rate);
+                smoothed_level = smoothed_level * ENVELOPE_SMOOTHING + level * (1.0 - ENVELOPE_SMOOTHING);
+
+                biofeedback.set_audio_level(smoothed_level);
+                biofeedback.set_peak_level(peak);
+                biofeedback.set_dominant_freq(freq);
+
+                match *biofeedback.mode.lock().unwrap() {
+                    BiofeedbackMode::Off => {}
+                    BiofeedbackMode::Amplitude => {
+                        params.set_amplitude(smoothed_level.min(MAX_AMPLITUDE));
+                    }
+                    BiofeedbackMode::Frequency => {
+                        let (lo, hi) = *biofeedback.frequency_range_hz.lock().unwrap();
+                        let nudged = lo + smoothed_level.clamp(0.0, 1.0) * (hi - lo);
+                        params.set_frequency(nudged.clamp(1.0, 60.0));
                     }
+                }
 
-                    phase += phase_increment;
-                    if phase >= 1.0 {
-                        phase -= 1.0;
+                if monitoring_running.load(Ordering::Relaxed) {
+                    let _ = app_handle.emit(
+                        "coherence-level",
+                        serde_json::json!({
+                            "audioLevel": smoothed_level,
+                            "peakLevel": peak,
+                            "dominantFreqHz": freq,
+                        }),
+                    );
+
+                    if monitoring_closed_loop.load(Ordering::Relaxed) {
+                        let (lo, hi) = *biofeedback.frequency_range_hz.lock().unwrap();
+                        let nudged = lo + smoothed_level.clamp(0.0, 1.0) * (hi - lo);
+                        params.set_frequency(nudged.clamp(1.0, 60.0));
                     }
                 }
             },
-            |err| eprintln!("Audio stream error: {}", err),
+            |err| eprintln!("Audio input stream error: {}", err),
             None,
         )
-        .map_err(|e| format!("Failed to build output stream: {}", e))?;
+        .map_err(|e| format!("Failed to build input stream: {}", e))?;
+
+    Ok(stream)
+}
+
+/// Open a WASAPI loopback capture reading back whatever the system's default
+/// output device is currently playing, and align the generator's beat rate
+/// (rather than its carrier, so the tone being entrained to stays put) to
+/// the captured audio's dominant frequency as a rough ambient tempo/pitch
+/// tracker. cpal doesn't expose `AUDCLNT_STREAMFLAGS_LOOPBACK` as a portable
+/// flag - on WASAPI a loopback endpoint surfaces to cpal as the output
+/// device's own input-capable config, so that's what's requested here; on
+/// every other host there's no equivalent and callers get a clear error
+/// instead of a stream that silently never calls back.
+fn create_loopback_stream(
+    app_handle: tauri::AppHandle,
+    params: Arc<AudioParams>,
+) -> Result<Stream, String> {
+    let host = cpal::default_host();
+    if host.id().name() != "WASAPI" {
+        return Err("Loopback capture is unsupported on this host".to_string());
+    }
+
+    let device = host
+        .default_output_device()
+        .ok_or("No audio output device found")?;
+
+    let config = device.default_input_config().map_err(|_| {
+        "Loopback capture is unsupported on this host".to_string()
+    })?;
+
+    let sample_rate = config.sample_rate().0 as f32;
+    let channels = config.channels() as usize;
+    let ring = InputRing::new();
+
+    let stream = device
+        .build_input_stream(
+            &config.into(),
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                for frame in data.chunks(channels) {
+                    ring.push(frame[0]);
+                }
+
+                let window = ring.latest(ANALYSIS_WINDOW);
+                if window.len() < ANALYSIS_WINDOW / 2 {
+                    return;
+                }
+
+                let freq = zero_crossing_frequency(&window, sample_rate);
+                if freq > 0.0 {
+                    params.set_beat_offset(freq.clamp(0.1, 20.0));
+                }
+
+                let _ = app_handle.emit(
+                    "loopback-tempo",
+                    serde_json::json!({ "dominantFreqHz": freq }),
+                );
+            },
+            |err| eprintln!("Loopback capture stream error: {}", err),
+            None,
+        )
+        .map_err(|e| format!("Failed to build loopback capture stream: {}", e))?;
 
     Ok(stream)
 }
@@ -348,6 +984,45 @@ fn set_waveform(state: tauri::State<AppState>, waveform: String) -> Result<(), S
     Ok(())
 }
 
+/// Set the binaural beat offset, in Hz, added to the right channel's
+/// frequency while the left channel stays at `frequency_hz`. 0.0 disables
+/// the effect and mirrors the carrier into both ears.
+#[tauri::command]
+fn set_binaural_beat(state: tauri::State<AppState>, beat_hz: f32) -> Result<(), String> {
+    state.audio_params.set_beat_offset(beat_hz);
+    Ok(())
+}
+
+/// Set the isochronic pulse rate, in Hz, that gates both channels'
+/// amplitude on and off at `MAX_DUTY_CYCLE`. 0.0 disables pulsing.
+#[tauri::command]
+fn set_isochronic_rate(state: tauri::State<AppState>, rate_hz: f32) -> Result<(), String> {
+    state.audio_params.set_isochronic_rate(rate_hz);
+    Ok(())
+}
+
+/// Lazily (re)build and start the primary output stream if it doesn't
+/// already exist. No-op while aggregate output devices are configured -
+/// those designate their own clock stream (see `set_aggregate_devices`),
+/// and starting the primary stream too would give the shared phase
+/// accumulator two concurrent clock writers racing each other.
+fn ensure_primary_output_stream(state: &AppState) -> Result<(), String> {
+    if !state.aggregate_streams.lock().map_err(|e| e.to_string())?.is_empty() {
+        return Ok(());
+    }
+
+    let mut stream_lock = state.audio_stream.lock().map_err(|e| e.to_string())?;
+    if stream_lock.is_none() {
+        let stream = create_audio_stream(
+            Arc::clone(&state.audio_params),
+            Arc::clone(&state.device_lost),
+        )?;
+        stream.play().map_err(|e| format!("Failed to start audio: {}", e))?;
+        *stream_lock = Some(stream);
+    }
+    Ok(())
+}
+
 /// Start audio session with safety timer
 #[tauri::command]
 fn start_session(
@@ -362,13 +1037,14 @@ fn start_session(
         }
     }
 
-    // Create and start audio stream if not exists
+    ensure_primary_output_stream(&state)?;
+
+    // Start any configured aggregate output devices (which replace the
+    // primary stream while configured)
     {
-        let mut stream_lock = state.audio_stream.lock().map_err(|e| e.to_string())?;
-        if stream_lock.is_none() {
-            let stream = create_audio_stream(Arc::clone(&state.audio_params))?;
-            stream.play().map_err(|e| format!("Failed to start audio: {}", e))?;
-            *stream_lock = Some(stream);
+        let streams = state.aggregate_streams.lock().map_err(|e| e.to_string())?;
+        for stream in streams.iter() {
+            stream.play().map_err(|e| format!("Failed to start aggregate stream: {}", e))?;
         }
     }
 
@@ -432,6 +1108,19 @@ fn start_session(
         *handle_lock = Some(timer_handle);
     }
 
+    // Start device-watcher, if not already running from a previous session
+    if !state.device_watcher_running.swap(true, Ordering::Relaxed) {
+        spawn_device_watcher(
+            app_handle,
+            Arc::clone(&state.audio_stream),
+            Arc::clone(&state.audio_config),
+            Arc::clone(&state.audio_params),
+            Arc::clone(&state.device_lost),
+            Arc::clone(&state.device_watcher_running),
+            Arc::clone(&state.stream_latency_ms),
+        );
+    }
+
     println!("[CoherenceCore] Session started");
     Ok(())
 }
@@ -442,9 +1131,22 @@ fn stop_session(state: tauri::State<AppState>) -> Result<(), String> {
     // Stop timer
     state.timer_running.store(false, Ordering::Relaxed);
 
+    // Stop the device watcher
+    state.device_watcher_running.store(false, Ordering::Relaxed);
+
     // Stop audio output
     state.audio_params.is_playing.store(false, Ordering::Relaxed);
 
+    // Tear down every aggregate output stream together with the primary one
+    {
+        let mut streams = state.aggregate_streams.lock().map_err(|e| e.to_string())?;
+        streams.clear();
+    }
+    {
+        let mut primary = state.audio_stream.lock().map_err(|e| e.to_string())?;
+        *primary = None;
+    }
+
     // Update session state
     {
         let mut session = state.session.lock().map_err(|e| e.to_string())?;
@@ -466,6 +1168,162 @@ fn stop_session(state: tauri::State<AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// Lazily open the shared microphone input stream if neither `start_biofeedback`
+/// nor `start_monitoring` has one running yet.
+fn ensure_input_stream(
+    state: &AppState,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let mut stream_lock = state.input_stream.lock().map_err(|e| e.to_string())?;
+    if stream_lock.is_none() {
+        let stream = create_input_stream(
+            app_handle,
+            Arc::clone(&state.audio_params),
+            Arc::clone(&state.biofeedback),
+            Arc::clone(&state.input_ring),
+            Arc::clone(&state.monitoring_running),
+            Arc::clone(&state.monitoring_closed_loop),
+        )?;
+        stream.play().map_err(|e| format!("Failed to start audio input: {}", e))?;
+        *stream_lock = Some(stream);
+    }
+    Ok(())
+}
+
+/// Tear down the shared input stream once neither biofeedback modulation nor
+/// monitoring is still using it.
+fn maybe_stop_input_stream(state: &AppState) -> Result<(), String> {
+    let biofeedback_active = *state.biofeedback.mode.lock().map_err(|e| e.to_string())? != BiofeedbackMode::Off;
+    let monitoring_active = state.monitoring_running.load(Ordering::Relaxed);
+
+    if !biofeedback_active && !monitoring_active {
+        let mut stream_lock = state.input_stream.lock().map_err(|e| e.to_string())?;
+        if let Some(stream) = stream_lock.take() {
+            drop(stream);
+        }
+    }
+
+    Ok(())
+}
+
+/// Start microphone capture and closed-loop biofeedback analysis. `mode`
+/// selects whether the measured envelope drives amplitude or frequency
+/// ("off" just reports metrics without modulating playback); `frequency_range_hz`
+/// bounds frequency mode (and `start_monitoring`'s closed loop). When it is
+/// omitted the previously set range is kept, initially 1-60 Hz.
+#[tauri::command]
+fn start_biofeedback(
+    state: tauri::State<AppState>,
+    app_handle: tauri::AppHandle,
+    mode: String,
+    frequency_range_hz: Option<(f32, f32)>,
+) -> Result<(), String> {
+    let parsed_mode: BiofeedbackMode = mode.parse()?;
+
+    {
+        let mut m = state.biofeedback.mode.lock().map_err(|e| e.to_string())?;
+        *m = parsed_mode;
+    }
+    if let Some(range) = frequency_range_hz {
+        let mut r = state.biofeedback.frequency_range_hz.lock().map_err(|e| e.to_string())?;
+        *r = range;
+    }
+
+    ensure_input_stream(&state, app_handle)?;
+
+    println!("[CoherenceCore] Biofeedback capture started");
+    Ok(())
+}
+
+/// Stop microphone capture and biofeedback modulation
+#[tauri::command]
+fn stop_biofeedback(state: tauri::State<AppState>) -> Result<(), String> {
+    {
+        let mut m = state.biofeedback.mode.lock().map_err(|e| e.to_string())?;
+        *m = BiofeedbackMode::Off;
+    }
+
+    maybe_stop_input_stream(&state)?;
+
+    println!("[CoherenceCore] Biofeedback capture stopped");
+    Ok(())
+}
+
+/// Read back the most recent microphone-derived metrics
+#[tauri::command]
+fn get_input_metrics(state: tauri::State<AppState>) -> InputMetrics {
+    InputMetrics {
+        audio_level: state.biofeedback.get_audio_level(),
+        dominant_freq_hz: state.biofeedback.get_dominant_freq(),
+    }
+}
+
+/// Start streaming real-time `coherence-level` events (peak/RMS level and
+/// dominant frequency) from the microphone input to the frontend. When
+/// `closed_loop` is true, also nudges `AudioParams::frequency` from the
+/// measured envelope within the active frequency range, the same way
+/// `start_biofeedback`'s `"frequency"` mode does - independently of whether
+/// biofeedback is itself enabled, so the two features can be mixed freely.
+#[tauri::command]
+fn start_monitoring(
+    state: tauri::State<AppState>,
+    app_handle: tauri::AppHandle,
+    closed_loop: bool,
+) -> Result<(), String> {
+    state.monitoring_closed_loop.store(closed_loop, Ordering::Relaxed);
+    state.monitoring_running.store(true, Ordering::Relaxed);
+
+    ensure_input_stream(&state, app_handle)?;
+
+    println!("[CoherenceCore] Coherence monitoring started");
+    Ok(())
+}
+
+/// Stop streaming `coherence-level` events and closed-loop entrainment
+#[tauri::command]
+fn stop_monitoring(state: tauri::State<AppState>) -> Result<(), String> {
+    state.monitoring_running.store(false, Ordering::Relaxed);
+    state.monitoring_closed_loop.store(false, Ordering::Relaxed);
+
+    maybe_stop_input_stream(&state)?;
+
+    println!("[CoherenceCore] Coherence monitoring stopped");
+    Ok(())
+}
+
+/// Start WASAPI loopback capture of the system's current audio output and
+/// set the generator's beat rate to its estimated dominant frequency,
+/// so the tone can entrain to whatever music is playing instead of only
+/// fixed presets. Fails with a clear error on hosts without loopback support
+/// rather than leaving the frontend waiting on a stream that never starts.
+#[tauri::command]
+fn start_loopback_capture(
+    state: tauri::State<AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let mut stream_lock = state.loopback_stream.lock().map_err(|e| e.to_string())?;
+    if stream_lock.is_none() {
+        let stream = create_loopback_stream(app_handle, Arc::clone(&state.audio_params))?;
+        stream.play().map_err(|e| format!("Failed to start loopback capture: {}", e))?;
+        *stream_lock = Some(stream);
+    }
+
+    println!("[CoherenceCore] Loopback capture started");
+    Ok(())
+}
+
+/// Stop loopback capture. The beat rate stays wherever it last settled.
+#[tauri::command]
+fn stop_loopback_capture(state: tauri::State<AppState>) -> Result<(), String> {
+    let mut stream_lock = state.loopback_stream.lock().map_err(|e| e.to_string())?;
+    if let Some(stream) = stream_lock.take() {
+        drop(stream);
+    }
+
+    println!("[CoherenceCore] Loopback capture stopped");
+    Ok(())
+}
+
 /// Get frequency presets
 #[tauri::command]
 fn get_presets() -> Vec<FrequencyPreset> {
@@ -581,12 +1439,23 @@ fn get_audio_hosts() -> Vec<AudioHostInfo> {
         .collect()
 }
 
-/// Get available audio output devices (includes class-compliant USB)
+/// Get available audio output devices (includes class-compliant USB).
+/// When `host_id` is given (see `get_audio_hosts`), only that backend is
+/// enumerated - e.g. to list just the ASIO devices after a user picks ASIO
+/// explicitly - instead of every host on the system.
 #[tauri::command]
-fn get_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+fn get_audio_devices(host_id: Option<String>) -> Result<Vec<AudioDeviceInfo>, String> {
     let mut devices = Vec::new();
 
-    for host_id in cpal::available_hosts() {
+    let hosts_to_scan: Vec<cpal::HostId> = match &host_id {
+        Some(wanted) => cpal::available_hosts()
+            .into_iter()
+            .filter(|h| h.name() == wanted)
+            .collect(),
+        None => cpal::available_hosts(),
+    };
+
+    for host_id in hosts_to_scan {
         let host = cpal::host_from_id(host_id)
             .map_err(|e| format!("Failed to get host: {}", e))?;
 
@@ -680,11 +1549,36 @@ fn get_audio_config(state: tauri::State<AppState>) -> Result<AudioConfig, String
     Ok(config.clone())
 }
 
+/// Check whether the currently configured output device is present
+#[tauri::command]
+fn get_device_status(state: tauri::State<AppState>) -> Result<DeviceStatus, String> {
+    let device_id = state.audio_config.lock().map_err(|e| e.to_string())?.device_id.clone();
+
+    let is_present = match &device_id {
+        Some(id) => get_audio_devices(None)?.iter().any(|d| &d.id == id),
+        None => true,
+    };
+
+    Ok(DeviceStatus { device_id, is_present })
+}
+
+/// Get the output latency in milliseconds last stored by
+/// `create_audio_stream_with_config`: the fixed buffer size negotiated in
+/// exclusive mode, or in shared mode the device's minimum advertised buffer
+/// size (the requested `buffer_size` if it advertises none). Streams opened
+/// by `create_audio_stream` do not update it; it reads 0.0 until one has
+/// been built through `create_audio_stream_with_config`.
+#[tauri::command]
+fn get_stream_latency_ms(state: tauri::State<AppState>) -> f32 {
+    f32::from_bits(state.stream_latency_ms.load(Ordering::Relaxed) as u32)
+}
+
 /// Set audio device and configuration
 #[tauri::command]
 fn set_audio_config(
     state: tauri::State<AppState>,
     device_id: Option<String>,
+    host_id: Option<String>,
     sample_rate: Option<u32>,
     buffer_size: Option<u32>,
 ) -> Result<(), String> {
@@ -702,12 +1596,22 @@ fn set_audio_config(
         }
     }
 
+    // Validate host, if given
+    if let Some(ref wanted) = host_id {
+        if !cpal::available_hosts().iter().any(|h| h.name() == wanted) {
+            return Err(format!("Audio host '{}' not found", wanted));
+        }
+    }
+
     // Update config
     {
         let mut config = state.audio_config.lock().map_err(|e| e.to_string())?;
         if let Some(id) = device_id {
             config.device_id = Some(id);
         }
+        if let Some(id) = host_id {
+            config.host_id = Some(id);
+        }
         if let Some(rate) = sample_rate {
             config.sample_rate = rate;
         }
@@ -732,6 +1636,8 @@ fn set_audio_config(
         let stream = create_audio_stream_with_config(
             Arc::clone(&state.audio_params),
             &config,
+            Arc::clone(&state.device_lost),
+            Arc::clone(&state.stream_latency_ms),
         )?;
         stream.play().map_err(|e| format!("Failed to start audio: {}", e))?;
 
@@ -743,12 +1649,176 @@ fn set_audio_config(
     Ok(())
 }
 
+/// Drive multiple output devices (e.g. several class-compliant USB
+/// transducers) from the same `AudioParams`, for synchronized multi-device
+/// setups. All devices must agree on a sample rate - mismatched rates are
+/// rejected up front rather than built and left to drift - since there is
+/// exactly one shared phase accumulator (`AudioParams::phase`) advanced by a
+/// single designated "clock" stream (the first device in `device_ids`); the
+/// rest only read that phase, so every transducer emits coherent samples
+/// instead of free-running against its own local accumulator.
+///
+/// Replaces any previously configured aggregate set. Pass an empty list to
+/// tear the aggregate down without configuring a new one.
+#[tauri::command]
+fn set_aggregate_devices(
+    state: tauri::State<AppState>,
+    device_ids: Vec<String>,
+) -> Result<(), String> {
+    // Tear down any existing aggregate before (re-)building
+    {
+        let mut streams = state.aggregate_streams.lock().map_err(|e| e.to_string())?;
+        streams.clear();
+    }
+
+    if device_ids.is_empty() {
+        // Falling back to the primary output device as the sole clock -
+        // rebuild it if a session is already playing.
+        if state.audio_params.is_playing.load(Ordering::Relaxed) {
+            ensure_primary_output_stream(&state)?;
+        }
+        return Ok(());
+    }
+
+    // The aggregate's first device becomes the new clock stream below, so
+    // the primary stream must not also be advancing the shared phase.
+    {
+        let mut primary = state.audio_stream.lock().map_err(|e| e.to_string())?;
+        *primary = None;
+    }
+
+    // Resolve every device up front and make sure they all agree on a
+    // sample rate before opening any stream.
+    let mut resolved = Vec::with_capacity(device_ids.len());
+    let mut common_sample_rate: Option<u32> = None;
+
+    for device_id in &device_ids {
+        let parts: Vec<&str> = device_id.splitn(2, ':').collect();
+        if parts.len() != 2 {
+            return Err(format!("Invalid device ID format: {}", device_id));
+        }
+        let (host_name, device_name) = (parts[0], parts[1]);
+
+        let host_id = cpal::available_hosts()
+            .into_iter()
+            .find(|h| h.name() == host_name)
+            .ok_or_else(|| format!("Audio host not found for '{}'", device_id))?;
+        let host = cpal::host_from_id(host_id).map_err(|e| format!("Failed to get host: {}", e))?;
+        let device = host
+            .output_devices()
+            .map_err(|e| format!("Failed to enumerate devices: {}", e))?
+            .find(|d| d.name().ok().as_deref() == Some(device_name))
+            .ok_or_else(|| format!("Device '{}' not found", device_id))?;
+
+        let supported_config = device
+            .default_output_config()
+            .map_err(|e| format!("Failed to get output config for '{}': {}", device_id, e))?;
+        let rate = supported_config.sample_rate().0;
+
+        match common_sample_rate {
+            None => common_sample_rate = Some(rate),
+            Some(expected) if expected != rate => {
+                return Err(format!(
+                    "Device '{}' runs at {} Hz, which doesn't match the aggregate's {} Hz - \
+                     all aggregate devices must share a sample rate",
+                    device_id, rate, expected
+                ));
+            }
+            _ => {}
+        }
+
+        resolved.push((device, supported_config));
+    }
+
+    // Restart the shared phase accumulator so the new aggregate begins in sync.
+    state.audio_params.set_phase(0.0);
+
+    let is_playing = state.audio_params.is_playing.load(Ordering::Relaxed);
+    let mut new_streams = Vec::with_capacity(resolved.len());
+
+    for (index, (device, supported_config)) in resolved.into_iter().enumerate() {
+        let is_clock = index == 0;
+        let stream = build_output_stream_for_format(
+            &device,
+            supported_config,
+            Arc::clone(&state.audio_params),
+            None,
+            Arc::clone(&state.device_lost),
+            None,
+            is_clock,
+        )?;
+
+        if is_playing {
+            stream
+                .play()
+                .map_err(|e| format!("Failed to start aggregate stream: {}", e))?;
+        }
+
+        new_streams.push(stream);
+    }
+
+    {
+        let mut streams = state.aggregate_streams.lock().map_err(|e| e.to_string())?;
+        *streams = new_streams;
+    }
+
+    println!(
+        "[CoherenceCore] Aggregate output configured across {} device(s)",
+        device_ids.len()
+    );
+    Ok(())
+}
+
+/// On Windows with the `asio` feature enabled, prefer the ASIO host for
+/// exclusive-mode acquisition; WASAPI (cpal's default host on Windows)
+/// already supports exclusive mode directly otherwise. Elsewhere there is
+/// no meaningfully distinct exclusive host, so we just use the default one.
+#[cfg(all(target_os = "windows", feature = "asio"))]
+fn preferred_exclusive_host() -> Host {
+    cpal::host_from_id(cpal::HostId::Asio).unwrap_or_else(|_| cpal::default_host())
+}
+
+#[cfg(not(all(target_os = "windows", feature = "asio")))]
+fn preferred_exclusive_host() -> Host {
+    cpal::default_host()
+}
+
+/// Clamp a requested buffer size into whatever range the device reports
+/// supporting; if the device doesn't report a range, trust the request.
+fn negotiate_buffer_size(range: Option<&cpal::SupportedBufferSize>, requested: u32) -> u32 {
+    match range {
+        Some(cpal::SupportedBufferSize::Range { min, max }) => requested.clamp(*min, *max),
+        _ => requested,
+    }
+}
+
 /// Create audio stream with specific device and configuration
+///
+/// In `ShareMode::Exclusive`, honors `config.sample_rate` and
+/// `config.buffer_size` exactly (clamped to the device's advertised
+/// `SupportedBufferSize::Range`) for the lowest achievable latency, falling
+/// back to shared mode if no matching configuration is found or the device
+/// refuses exclusive acquisition. The resulting latency (exact in exclusive
+/// mode, estimated from the device's minimum advertised buffer size in
+/// shared mode) is stored in `stream_latency_ms` for `get_stream_latency_ms`
+/// to report back to the UI.
 fn create_audio_stream_with_config(
     params: Arc<AudioParams>,
     config: &AudioConfig,
+    device_lost: Arc<AtomicBool>,
+    stream_latency_ms: Arc<AtomicU64>,
 ) -> Result<Stream, String> {
-    let host = cpal::default_host();
+    let host = if config.share_mode == ShareMode::Exclusive {
+        preferred_exclusive_host()
+    } else if let Some(ref host_id) = config.host_id {
+        let id = cpal::available_hosts()
+            .into_iter()
+            .find(|h| h.name() == host_id)
+            .ok_or_else(|| format!("Audio host '{}' not found", host_id))?;
+        cpal::host_from_id(id).map_err(|e| format!("Failed to get host: {}", e))?
+    } else {
+        cpal::default_host()
+    };
 
     // Get device (default or specified)
     let device = if let Some(ref device_id) = config.device_id {
@@ -783,50 +1853,179 @@ fn create_audio_stream_with_config(
 
     println!("[CoherenceCore] Using audio device: {:?}", device.name());
 
-    // Get supported config
+    if config.share_mode == ShareMode::Exclusive {
+        let exclusive_config = device
+            .supported_output_configs()
+            .ok()
+            .and_then(|mut ranges| {
+                ranges.find(|r| {
+                    r.min_sample_rate().0 <= config.sample_rate && config.sample_rate <= r.max_sample_rate().0
+                })
+            });
+
+        if let Some(range) = exclusive_config {
+            let buffer_size = negotiate_buffer_size(range.buffer_size(), config.buffer_size);
+            let supported_config = range.with_sample_rate(cpal::SampleRate(config.sample_rate));
+
+            match build_output_stream_for_format(
+                &device,
+                supported_config,
+                Arc::clone(&params),
+                Some(config.sample_rate as f32),
+                Arc::clone(&device_lost),
+                Some(buffer_size),
+                true,
+            ) {
+                Ok(stream) => {
+                    let latency_ms = buffer_size as f32 / config.sample_rate as f32 * 1000.0;
+                    stream_latency_ms.store(latency_ms.to_bits() as u64, Ordering::Relaxed);
+                    return Ok(stream);
+                }
+                Err(e) => {
+                    eprintln!(
+                        "[CoherenceCore] Exclusive-mode acquisition failed ({e}), falling back to shared mode"
+                    );
+                }
+            }
+        } else {
+            eprintln!("[CoherenceCore] Device has no exclusive-mode config matching the requested sample rate, falling back to shared mode");
+        }
+    }
+
+    // Get supported config (shared mode, or exclusive-mode fallback)
     let supported_config = device
         .default_output_config()
         .map_err(|e| format!("Failed to get output config: {}", e))?;
 
-    let sample_rate = config.sample_rate as f32;
-    let channels = supported_config.channels() as usize;
+    let buffer_size_hint = match supported_config.buffer_size() {
+        Some(cpal::SupportedBufferSize::Range { min, .. }) => *min,
+        _ => config.buffer_size,
+    };
+    let latency_ms = buffer_size_hint as f32 / config.sample_rate as f32 * 1000.0;
+    stream_latency_ms.store(latency_ms.to_bits() as u64, Ordering::Relaxed);
+
+    build_output_stream_for_format(
+        &device,
+        supported_config,
+        params,
+        Some(config.sample_rate as f32),
+        device_lost,
+        None,
+        true,
+    )
+}
 
-    let mut phase: f32 = 0.0;
+/// Watch for the configured output device disappearing mid-session, either
+/// reported directly via `device_lost` (set by the stream's error callback
+/// on `DeviceNotAvailable` - the WASAPI `AUDCLNT_E_DEVICE_INVALIDATED` case
+/// surfaces this way) or inferred by no longer turning up in
+/// `get_audio_devices()`. On loss, emits `audio-device-lost`, tears down the
+/// dead stream, and retries rebuilding it against the system default device
+/// (via `create_audio_stream`) with exponential backoff, up to
+/// `MAX_RECOVERY_RETRIES` attempts, so a persistently missing device doesn't
+/// spin forever - past the cap it emits `audio-device-recovery-failed` and
+/// waits for the device to turn up in `get_audio_devices()` again rather
+/// than continuing to retry. Once the configured device reappears, swaps
+/// back to it automatically via `create_audio_stream_with_config` and emits
+/// `audio-device-restored`. `AudioParams` (frequency/amplitude/waveform/
+/// is_playing) lives independently of any particular `Stream`, so every
+/// rebuild here picks it back up exactly where it left off. Session timer
+/// state (`elapsed_ms`/`remaining_ms`) is untouched throughout, since it is
+/// derived from `session_start_ms` rather than the stream itself.
+fn spawn_device_watcher(
+    app_handle: tauri::AppHandle,
+    audio_stream: Arc<Mutex<Option<Stream>>>,
+    audio_config: Arc<Mutex<AudioConfig>>,
+    audio_params: Arc<AudioParams>,
+    device_lost: Arc<AtomicBool>,
+    watcher_running: Arc<AtomicBool>,
+    stream_latency_ms: Arc<AtomicU64>,
+) {
+    thread::spawn(move || {
+        let mut fallen_back = false;
+        let mut recovery_attempts: u32 = 0;
+
+        while watcher_running.load(Ordering::Relaxed) {
+            thread::sleep(DEVICE_WATCH_INTERVAL);
+
+            let configured_id = audio_config.lock().unwrap().device_id.clone();
+            let still_present = match &configured_id {
+                Some(id) => get_audio_devices(None)
+                    .map(|devices| devices.iter().any(|d| &d.id == id))
+                    .unwrap_or(true),
+                None => true,
+            };
 
-    let stream = device
-        .build_output_stream(
-            &supported_config.into(),
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                let frequency = params.get_frequency();
-                let amplitude = params.get_amplitude();
-                let waveform = *params.waveform.lock().unwrap();
-                let is_playing = params.is_playing.load(Ordering::Relaxed);
+            if !fallen_back && (device_lost.swap(false, Ordering::Relaxed) || !still_present) {
+                fallen_back = true;
+                recovery_attempts = 0;
+                let _ = app_handle.emit("audio-device-lost", configured_id.clone());
 
-                let phase_increment = frequency / sample_rate;
+                *audio_stream.lock().unwrap() = None;
+            }
 
-                for frame in data.chunks_mut(channels) {
-                    let sample = if is_playing {
-                        generate_sample(waveform, phase, amplitude)
-                    } else {
-                        0.0
-                    };
-
-                    for channel in frame.iter_mut() {
-                        *channel = sample;
-                    }
+            if !fallen_back {
+                continue;
+            }
+
+            let has_stream = audio_stream.lock().unwrap().is_some();
+
+            if !has_stream
+                && audio_params.is_playing.load(Ordering::Relaxed)
+                && recovery_attempts < MAX_RECOVERY_RETRIES
+            {
+                thread::sleep(Duration::from_millis(
+                    RECOVERY_BACKOFF_BASE_MS * 2u64.pow(recovery_attempts),
+                ));
 
-                    phase += phase_increment;
-                    if phase >= 1.0 {
-                        phase -= 1.0;
+                let rebuilt = if let Ok(fallback) =
+                    create_audio_stream(Arc::clone(&audio_params), Arc::clone(&device_lost))
+                {
+                    fallback.play().is_ok().then_some(fallback)
+                } else {
+                    None
+                };
+
+                match rebuilt {
+                    Some(stream) => {
+                        *audio_stream.lock().unwrap() = Some(stream);
+                        recovery_attempts = 0;
+
+                        // No specific device is configured, so the default
+                        // device IS the target - a healthy fallback stream
+                        // means recovery is already complete; re-arm the
+                        // loss guard instead of waiting on a restore branch
+                        // that only fires for a configured `device_id`.
+                        if configured_id.is_none() {
+                            fallen_back = false;
+                            let _ = app_handle.emit("audio-device-restored", configured_id.clone());
+                        }
+                    }
+                    None => {
+                        recovery_attempts += 1;
+                        if recovery_attempts == MAX_RECOVERY_RETRIES {
+                            let _ = app_handle.emit("audio-device-recovery-failed", configured_id.clone());
+                        }
                     }
                 }
-            },
-            |err| eprintln!("Audio stream error: {}", err),
-            None,
-        )
-        .map_err(|e| format!("Failed to build output stream: {}", e))?;
-
-    Ok(stream)
+            } else if configured_id.is_some() && still_present {
+                let config = audio_config.lock().unwrap().clone();
+                if let Ok(restored) = create_audio_stream_with_config(
+                    Arc::clone(&audio_params),
+                    &config,
+                    Arc::clone(&device_lost),
+                    Arc::clone(&stream_latency_ms),
+                ) {
+                    if restored.play().is_ok() {
+                        *audio_stream.lock().unwrap() = Some(restored);
+                        fallen_back = false;
+                        recovery_attempts = 0;
+                        let _ = app_handle.emit("audio-device-restored", config.device_id.clone());
+                    }
+                }
+            }
+        }
+    });
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -839,6 +2038,8 @@ pub fn run() {
             set_frequency,
             set_amplitude,
             set_waveform,
+            set_binaural_beat,
+            set_isochronic_rate,
             start_session,
             stop_session,
             get_presets,
@@ -850,6 +2051,18 @@ pub fn run() {
             get_audio_devices,
             get_audio_config,
             set_audio_config,
+            get_device_status,
+            get_stream_latency_ms,
+            set_aggregate_devices,
+            // Closed-loop biofeedback
+            start_biofeedback,
+            stop_biofeedback,
+            get_input_metrics,
+            start_monitoring,
+            stop_monitoring,
+            // Ambient-music entrainment
+            start_loopback_capture,
+            stop_loopback_capture,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
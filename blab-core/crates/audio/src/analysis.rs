@@ -0,0 +1,100 @@
+//! Live audio analysis
+//!
+//! Small, allocation-free helpers for deriving bio-parameters from captured
+//! microphone frames: signal level (RMS) and a fundamental-frequency
+//! estimate via autocorrelation.
+
+/// Vocal fundamental range this crate tunes pitch detection for.
+const MIN_VOICE_HZ: f32 = 80.0;
+const MAX_VOICE_HZ: f32 = 400.0;
+
+/// Autocorrelation peak, normalized against zero-lag energy, below which a
+/// frame is treated as unvoiced.
+const VOICED_CONFIDENCE_THRESHOLD: f32 = 0.3;
+
+/// Root-mean-square level of a captured frame, 0.0 (silence) upward.
+pub fn rms_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f32 = samples.iter().map(|&s| s * s).sum();
+    (sum_sq / samples.len() as f32).sqrt()
+}
+
+/// Estimate the fundamental frequency of a captured frame via
+/// autocorrelation, searching lags corresponding to `MIN_VOICE_HZ` ..
+/// `MAX_VOICE_HZ`. Returns 0.0 when the frame looks unvoiced (the strongest
+/// lag's normalized autocorrelation falls below `VOICED_CONFIDENCE_THRESHOLD`).
+pub fn estimate_pitch_autocorrelation(samples: &[f32], sample_rate: f32) -> f32 {
+    if samples.len() < 4 {
+        return 0.0;
+    }
+
+    let min_lag = (sample_rate / MAX_VOICE_HZ).floor().max(1.0) as usize;
+    let max_lag = (sample_rate / MIN_VOICE_HZ).ceil() as usize;
+    let max_lag = max_lag.min(samples.len() - 1);
+    if min_lag >= max_lag {
+        return 0.0;
+    }
+
+    let zero_lag_energy: f32 = samples.iter().map(|&s| s * s).sum();
+    if zero_lag_energy <= f32::EPSILON {
+        return 0.0;
+    }
+
+    let mut best_lag = 0usize;
+    let mut best_correlation = 0.0f32;
+
+    for lag in min_lag..=max_lag {
+        let mut sum = 0.0f32;
+        for n in 0..(samples.len() - lag) {
+            sum += samples[n] * samples[n + lag];
+        }
+        let normalized = sum / zero_lag_energy;
+        if normalized > best_correlation {
+            best_correlation = normalized;
+            best_lag = lag;
+        }
+    }
+
+    if best_lag == 0 || best_correlation < VOICED_CONFIDENCE_THRESHOLD {
+        return 0.0;
+    }
+
+    sample_rate / best_lag as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rms_level_silence() {
+        let samples = vec![0.0; 256];
+        assert_eq!(rms_level(&samples), 0.0);
+    }
+
+    #[test]
+    fn test_rms_level_full_scale_square() {
+        let samples: Vec<f32> = (0..256).map(|n| if n % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        assert!((rms_level(&samples) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pitch_detects_known_sine() {
+        let sample_rate = 48_000.0;
+        let target_hz = 150.0;
+        let samples: Vec<f32> = (0..2048)
+            .map(|n| (2.0 * std::f32::consts::PI * target_hz * n as f32 / sample_rate).sin())
+            .collect();
+
+        let pitch = estimate_pitch_autocorrelation(&samples, sample_rate);
+        assert!((pitch - target_hz).abs() < 2.0, "estimated {pitch} Hz");
+    }
+
+    #[test]
+    fn test_pitch_reports_zero_for_silence() {
+        let samples = vec![0.0; 2048];
+        assert_eq!(estimate_pitch_autocorrelation(&samples, 48_000.0), 0.0);
+    }
+}
@@ -0,0 +1,165 @@
+//! Clock-timestamped analysis queue for audio-visual synchronization
+//!
+//! Each processed output buffer is tagged with the monotonic sample count
+//! at which it begins playing, and pushed into a small lock-free ring. A
+//! render loop running on its own clock can then ask for whichever
+//! snapshot is actually audible *now* — via [`ClockedQueue::pop_next`] —
+//! instead of always reacting to whatever the audio thread most recently
+//! produced. Callers should subtract their output latency, in samples
+//! (derived from [`super::engine::AudioEngine::get_latency_ms`]), from
+//! their presentation clock before calling `pop_next`, since a buffer
+//! tagged with position N takes roughly that long to actually reach the
+//! speaker.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+const QUEUE_DEPTH: usize = 8;
+
+struct Slot<T: Copy> {
+    /// 0 = never written. Even (non-zero) = stable, readable. Odd = a
+    /// writer is publishing.
+    generation: AtomicU32,
+    sample_position: AtomicU64,
+    value: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: `value`/`sample_position` are only mutated inside `push` while
+// `generation` is held odd, and only read after observing matching, even
+// generations on both sides of the read.
+unsafe impl<T: Copy> Sync for Slot<T> {}
+
+struct Inner<T: Copy> {
+    slots: [Slot<T>; QUEUE_DEPTH],
+    write_cursor: AtomicU64,
+}
+
+/// A bounded, lock-free ring of clock-tagged snapshots. Pushing never
+/// blocks; the oldest of the last `QUEUE_DEPTH` snapshots is silently
+/// overwritten once the ring wraps.
+pub struct ClockedQueue<T: Copy> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Copy> Clone for ClockedQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Copy> ClockedQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                slots: std::array::from_fn(|_| Slot {
+                    generation: AtomicU32::new(0),
+                    sample_position: AtomicU64::new(0),
+                    value: UnsafeCell::new(None),
+                }),
+                write_cursor: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Push a snapshot tagged with the sample position at which its buffer
+    /// begins playing. Wait-free; never blocks the caller.
+    pub fn push(&self, sample_position: u64, value: T) {
+        let cursor = self.inner.write_cursor.fetch_add(1, Ordering::AcqRel);
+        let slot = &self.inner.slots[cursor as usize % QUEUE_DEPTH];
+
+        let gen = slot.generation.fetch_add(1, Ordering::AcqRel);
+        unsafe {
+            *slot.value.get() = Some(value);
+        }
+        slot.sample_position.store(sample_position, Ordering::Relaxed);
+        slot.generation.store(gen.wrapping_add(2), Ordering::Release);
+    }
+
+    /// The most recently pushed snapshot, regardless of its timestamp.
+    pub fn pop_latest(&self) -> Option<(u64, T)> {
+        let cursor = self.inner.write_cursor.load(Ordering::Acquire);
+        if cursor == 0 {
+            return None;
+        }
+        self.read_slot((cursor - 1) as usize % QUEUE_DEPTH)
+    }
+
+    /// The buffered snapshot whose sample position is closest to, but not
+    /// after, `presentation_sample` — i.e. whatever is actually audible at
+    /// that point in the stream. Falls back to the latest snapshot if
+    /// every buffered entry is still in the future (e.g. right at startup).
+    pub fn pop_next(&self, presentation_sample: u64) -> Option<(u64, T)> {
+        let mut best: Option<(u64, T)> = None;
+        for index in 0..QUEUE_DEPTH {
+            let Some((pos, value)) = self.read_slot(index) else {
+                continue;
+            };
+            if pos > presentation_sample {
+                continue;
+            }
+            if best.map(|(best_pos, _)| pos > best_pos).unwrap_or(true) {
+                best = Some((pos, value));
+            }
+        }
+        best.or_else(|| self.pop_latest())
+    }
+
+    fn read_slot(&self, index: usize) -> Option<(u64, T)> {
+        let slot = &self.inner.slots[index];
+        loop {
+            let g1 = slot.generation.load(Ordering::Acquire);
+            if g1 & 1 != 0 {
+                std::hint::spin_loop();
+                continue;
+            }
+            if g1 == 0 {
+                return None;
+            }
+            let pos = slot.sample_position.load(Ordering::Relaxed);
+            let value = unsafe { *slot.value.get() };
+            let g2 = slot.generation.load(Ordering::Acquire);
+            if g1 == g2 {
+                return value.map(|v| (pos, v));
+            }
+        }
+    }
+}
+
+impl<T: Copy> Default for ClockedQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pop_latest_tracks_most_recent_push() {
+        let queue = ClockedQueue::new();
+        queue.push(0, 1.0f32);
+        queue.push(256, 2.0f32);
+        assert_eq!(queue.pop_latest(), Some((256, 2.0)));
+    }
+
+    #[test]
+    fn test_pop_next_picks_closest_non_future_entry() {
+        let queue = ClockedQueue::new();
+        queue.push(0, 1.0f32);
+        queue.push(256, 2.0f32);
+        queue.push(512, 3.0f32);
+
+        assert_eq!(queue.pop_next(300), Some((256, 2.0)));
+    }
+
+    #[test]
+    fn test_pop_next_falls_back_to_latest_when_all_future() {
+        let queue = ClockedQueue::new();
+        queue.push(1000, 1.0f32);
+        assert_eq!(queue.pop_next(0), Some((1000, 1.0)));
+    }
+}
@@ -0,0 +1,117 @@
+//! Lock-free "latest value" mailboxes for passing small `Copy` snapshots
+//! between the real-time audio callback and control-thread API calls.
+//!
+//! The audio thread must never block on a lock, so parameter updates (and
+//! analysis results read back out) flow through a single-slot cell guarded
+//! by a generation counter (a seqlock): writers publish without blocking,
+//! and readers retry only in the vanishingly rare case they raced a writer
+//! mid-publish. Overwrite-newest semantics fall out naturally — the cell
+//! always holds (or is about to hold) the most recently sent value.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+struct Cell<T: Copy> {
+    /// Even = stable, readable. Odd = a writer is publishing.
+    generation: AtomicU32,
+    value: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: `value` is only ever mutated inside `Sender::send` while
+// `generation` is held odd, and only read after observing an even,
+// matching generation on both sides of the read.
+unsafe impl<T: Copy> Sync for Cell<T> {}
+
+/// Publishes snapshots into a mailbox. Never blocks.
+pub struct Sender<T: Copy> {
+    cell: Arc<Cell<T>>,
+}
+
+/// Reads the most recently published snapshot from a mailbox. Never blocks.
+pub struct Receiver<T: Copy> {
+    cell: Arc<Cell<T>>,
+}
+
+impl<T: Copy> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: Arc::clone(&self.cell),
+        }
+    }
+}
+
+impl<T: Copy> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: Arc::clone(&self.cell),
+        }
+    }
+}
+
+impl<T: Copy> Sender<T> {
+    /// Publish a new value. Wait-free; never blocks the caller.
+    pub fn send(&self, value: T) {
+        let gen = self.cell.generation.fetch_add(1, Ordering::AcqRel);
+        unsafe {
+            *self.cell.value.get() = Some(value);
+        }
+        self.cell
+            .generation
+            .store(gen.wrapping_add(2), Ordering::Release);
+    }
+}
+
+impl<T: Copy> Receiver<T> {
+    /// Read the most recently published value, or `None` if nothing has
+    /// been sent yet. Spins only if caught mid-publish, which requires a
+    /// writer to be preempted between `send`'s two atomic ops.
+    pub fn recv(&self) -> Option<T> {
+        loop {
+            let g1 = self.cell.generation.load(Ordering::Acquire);
+            if g1 & 1 != 0 {
+                std::hint::spin_loop();
+                continue;
+            }
+            let value = unsafe { *self.cell.value.get() };
+            let g2 = self.cell.generation.load(Ordering::Acquire);
+            if g1 == g2 {
+                return value;
+            }
+        }
+    }
+}
+
+/// Construct a connected sender/receiver pair sharing one mailbox slot.
+pub fn mailbox<T: Copy>() -> (Sender<T>, Receiver<T>) {
+    let cell = Arc::new(Cell {
+        generation: AtomicU32::new(0),
+        value: UnsafeCell::new(None),
+    });
+    (
+        Sender {
+            cell: Arc::clone(&cell),
+        },
+        Receiver { cell },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mailbox_starts_empty() {
+        let (_tx, rx) = mailbox::<f32>();
+        assert_eq!(rx.recv(), None);
+    }
+
+    #[test]
+    fn test_mailbox_overwrite_newest() {
+        let (tx, rx) = mailbox::<i32>();
+        tx.send(1);
+        tx.send(2);
+        tx.send(3);
+        assert_eq!(rx.recv(), Some(3));
+    }
+}
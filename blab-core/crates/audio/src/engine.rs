@@ -1,50 +1,122 @@
 //! Audio Engine - Core audio processing system
 
+use crate::analysis::{estimate_pitch_autocorrelation, rms_level};
+use crate::clock::ClockedQueue;
+use crate::control::{self, Receiver, Sender};
+use crate::oscillator::{Oscillator, OscillatorBank, OscillatorSlot, Waveform, MAX_OSCILLATORS};
+use crate::resample::StreamResampler;
 use crate::{AudioConfig, BioParameters};
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex};
+
+/// Captured-audio derived parameters, read back from the engine's live
+/// microphone analysis (see [`AudioEngine::start_input`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CapturedAudioParams {
+    /// RMS level of the most recently captured input frame (0.0 - 1.0-ish)
+    pub audio_level: f32,
+    /// Autocorrelation pitch estimate (Hz), 0.0 when unvoiced
+    pub voice_pitch: f32,
+}
 
 /// Main audio engine
+///
+/// The real-time callback never takes a lock: bio-parameter updates and
+/// captured-audio analysis cross the control/audio-thread boundary through
+/// the lock-free mailboxes in [`crate::control`], and a fresh
+/// [`AudioProcessor`] is built from them each time the output stream starts.
 pub struct AudioEngine {
     config: AudioConfig,
     stream: Option<cpal::Stream>,
-    processor: Arc<Mutex<AudioProcessor>>,
+    input_stream: Option<cpal::Stream>,
+    bio_tx: Sender<BioParameters>,
+    bio_rx: Receiver<BioParameters>,
+    capture_tx: Sender<CapturedAudioParams>,
+    capture_rx: Receiver<CapturedAudioParams>,
+    status_tx: Sender<CapturedAudioParams>,
+    status_rx: Receiver<CapturedAudioParams>,
+    oscillator_bank: OscillatorBank,
+    osc_tx: Sender<OscillatorBank>,
+    osc_rx: Receiver<OscillatorBank>,
+    clock_queue: ClockedQueue<BioParameters>,
 }
 
 impl AudioEngine {
     /// Create new audio engine
     pub fn new(config: AudioConfig) -> Result<Self> {
-        let processor = Arc::new(Mutex::new(AudioProcessor::new(&config)));
+        let (bio_tx, bio_rx) = control::mailbox::<BioParameters>();
+        let (capture_tx, capture_rx) = control::mailbox::<CapturedAudioParams>();
+        let (status_tx, status_rx) = control::mailbox::<CapturedAudioParams>();
+        let (osc_tx, osc_rx) = control::mailbox::<OscillatorBank>();
 
         Ok(Self {
             config,
             stream: None,
-            processor,
+            input_stream: None,
+            bio_tx,
+            bio_rx,
+            capture_tx,
+            capture_rx,
+            status_tx,
+            status_rx,
+            oscillator_bank: OscillatorBank::default(),
+            osc_tx,
+            osc_rx,
+            clock_queue: ClockedQueue::new(),
         })
     }
 
-    /// Start audio processing
+    /// Start audio processing on the default output device
     pub fn start(&mut self) -> Result<()> {
         let host = cpal::default_host();
         let device = host
             .default_output_device()
             .context("No output device available")?;
+        self.start_on_device(device)
+    }
+
+    /// Start audio processing on a specific output device, identified by
+    /// the `id` returned from [`Self::list_output_devices`].
+    pub fn start_with_device(&mut self, device_id: &str) -> Result<()> {
+        let host = cpal::default_host();
+        let device = host
+            .output_devices()
+            .context("Failed to enumerate output devices")?
+            .find(|d| d.name().map(|n| n == device_id).unwrap_or(false))
+            .with_context(|| format!("No output device matching id '{device_id}'"))?;
+        self.start_on_device(device)
+    }
+
+    fn start_on_device(&mut self, device: cpal::Device) -> Result<()> {
+        // The device may not actually support the engine's configured rate
+        // (common on shared/WASAPI-style outputs); query what it negotiated
+        // and drive the stream at that rate, resampling internally if it
+        // differs from the engine's processing rate.
+        let supported_config = device
+            .default_output_config()
+            .context("No supported output config for device")?;
+        let device_sample_rate = supported_config.sample_rate().0;
 
         let config = cpal::StreamConfig {
             channels: self.config.output_channels,
-            sample_rate: cpal::SampleRate(self.config.sample_rate),
+            sample_rate: cpal::SampleRate(device_sample_rate),
             buffer_size: cpal::BufferSize::Fixed(self.config.buffer_size),
         };
 
-        let processor = Arc::clone(&self.processor);
+        let mut processor = AudioProcessor::new(
+            &self.config,
+            self.bio_rx.clone(),
+            self.capture_rx.clone(),
+            self.status_tx.clone(),
+            self.osc_rx.clone(),
+            self.clock_queue.clone(),
+        );
+        processor.configure_resampling(device_sample_rate as f32, self.config.buffer_size as usize);
 
         let stream = device.build_output_stream(
             &config,
             move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                if let Ok(mut proc) = processor.lock() {
-                    proc.process(data);
-                }
+                processor.process(data);
             },
             |err| eprintln!("Audio stream error: {}", err),
             None,
@@ -61,11 +133,86 @@ impl AudioEngine {
         self.stream = None;
     }
 
-    /// Update bio-reactive parameters
+    /// Start capturing the default input device and feeding its analysis
+    /// (signal level, voice pitch) back into the processor's bio-parameters,
+    /// so the engine can drive itself from a live microphone instead of
+    /// requiring a host to call [`Self::update_bio_parameters`].
+    pub fn start_input(&mut self) -> Result<()> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .context("No input device available")?;
+
+        let config = cpal::StreamConfig {
+            channels: self.config.input_channels,
+            sample_rate: cpal::SampleRate(self.config.sample_rate),
+            buffer_size: cpal::BufferSize::Fixed(self.config.buffer_size),
+        };
+
+        let sample_rate = self.config.sample_rate as f32;
+        let channels = self.config.input_channels.max(1) as usize;
+        let capture_tx = self.capture_tx.clone();
+
+        let stream = device.build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                let audio_level = rms_level(data);
+                // `data` is interleaved; estimate pitch on the first channel
+                // so lags are measured in frames at `sample_rate`.
+                let first_channel: Vec<f32> = data.iter().step_by(channels).copied().collect();
+                let voice_pitch = estimate_pitch_autocorrelation(&first_channel, sample_rate);
+                capture_tx.send(CapturedAudioParams {
+                    audio_level,
+                    voice_pitch,
+                });
+            },
+            |err| eprintln!("Audio input stream error: {}", err),
+            None,
+        )?;
+
+        stream.play()?;
+        self.input_stream = Some(stream);
+
+        Ok(())
+    }
+
+    /// Stop microphone capture
+    pub fn stop_input(&mut self) {
+        self.input_stream = None;
+    }
+
+    /// Read back the `audio_level`/`voice_pitch` the output processor applied
+    /// to its most recent buffer: microphone-derived while input capture is
+    /// running, otherwise whatever `update_bio_parameters` last supplied.
+    /// All zeros until the output stream has processed a buffer.
+    pub fn captured_params(&self) -> CapturedAudioParams {
+        self.status_rx.recv().unwrap_or_default()
+    }
+
+    /// Update bio-reactive parameters. Wait-free: publishes into the
+    /// control-to-audio mailbox without ever blocking on the audio thread.
     pub fn update_bio_parameters(&self, params: BioParameters) {
-        if let Ok(mut proc) = self.processor.lock() {
-            proc.update_bio_parameters(params);
+        self.bio_tx.send(params);
+    }
+
+    /// Configure one oscillator in the bank mixed into the output. Out-of-range
+    /// `index` (`>= MAX_OSCILLATORS`) is ignored.
+    pub fn set_waveform(&mut self, index: usize, waveform: Waveform, base_freq: f32, volume: f32) {
+        if index >= MAX_OSCILLATORS {
+            return;
         }
+        self.oscillator_bank.slots[index] = OscillatorSlot {
+            waveform,
+            freq: base_freq,
+            volume,
+        };
+        self.osc_tx.send(self.oscillator_bank);
+    }
+
+    /// The clock-tagged analysis queue a render loop can poll to pick the
+    /// snapshot that matches what's currently audible (see [`ClockedQueue::pop_next`]).
+    pub fn clock_queue(&self) -> &ClockedQueue<BioParameters> {
+        &self.clock_queue
     }
 
     /// Get current latency (ms)
@@ -74,42 +221,243 @@ impl AudioEngine {
         let rate = self.config.sample_rate as f32;
         (samples / rate) * 1000.0
     }
+
+    /// Enumerate available output devices and the sample-rate/channel
+    /// ranges they support.
+    pub fn list_output_devices() -> Vec<DeviceInfo> {
+        let host = cpal::default_host();
+        let Ok(devices) = host.output_devices() else {
+            return Vec::new();
+        };
+
+        devices
+            .filter_map(|device| {
+                let name = device.name().ok()?;
+                let configs = device.supported_output_configs().ok()?;
+                DeviceInfo::from_configs(name, configs)
+            })
+            .collect()
+    }
+
+    /// Enumerate available input devices and the sample-rate/channel
+    /// ranges they support.
+    pub fn list_input_devices() -> Vec<DeviceInfo> {
+        let host = cpal::default_host();
+        let Ok(devices) = host.input_devices() else {
+            return Vec::new();
+        };
+
+        devices
+            .filter_map(|device| {
+                let name = device.name().ok()?;
+                let configs = device.supported_input_configs().ok()?;
+                DeviceInfo::from_configs(name, configs)
+            })
+            .collect()
+    }
+}
+
+/// Describes an audio device available on the host.
+///
+/// `id` is the device's cpal name — cpal exposes no other cross-platform
+/// stable identifier, so the name doubles as the handle passed back into
+/// [`AudioEngine::start_with_device`].
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub id: String,
+    pub name: String,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    pub min_channels: u16,
+    pub max_channels: u16,
+}
+
+impl DeviceInfo {
+    fn from_configs(
+        name: String,
+        configs: impl Iterator<Item = cpal::SupportedStreamConfigRange>,
+    ) -> Option<Self> {
+        let mut min_sample_rate = u32::MAX;
+        let mut max_sample_rate = 0u32;
+        let mut min_channels = u16::MAX;
+        let mut max_channels = 0u16;
+
+        for config in configs {
+            min_sample_rate = min_sample_rate.min(config.min_sample_rate().0);
+            max_sample_rate = max_sample_rate.max(config.max_sample_rate().0);
+            min_channels = min_channels.min(config.channels());
+            max_channels = max_channels.max(config.channels());
+        }
+
+        if max_sample_rate == 0 {
+            return None;
+        }
+
+        Some(Self {
+            id: name.clone(),
+            name,
+            min_sample_rate,
+            max_sample_rate,
+            min_channels,
+            max_channels,
+        })
+    }
 }
 
 /// Audio processor (DSP)
 pub struct AudioProcessor {
     sample_rate: f32,
     bio_params: BioParameters,
-    phase: f32,  // For test tone
+    oscillators: [Oscillator; MAX_OSCILLATORS],
+    resampler: Option<StreamResampler>,
+    bio_rx: Receiver<BioParameters>,
+    capture_rx: Receiver<CapturedAudioParams>,
+    status_tx: Sender<CapturedAudioParams>,
+    osc_rx: Receiver<OscillatorBank>,
+    clock_queue: ClockedQueue<BioParameters>,
+    samples_written: u64,
 }
 
 impl AudioProcessor {
-    pub fn new(config: &AudioConfig) -> Self {
+    pub fn new(
+        config: &AudioConfig,
+        bio_rx: Receiver<BioParameters>,
+        capture_rx: Receiver<CapturedAudioParams>,
+        status_tx: Sender<CapturedAudioParams>,
+        osc_rx: Receiver<OscillatorBank>,
+        clock_queue: ClockedQueue<BioParameters>,
+    ) -> Self {
+        let default_bank = OscillatorBank::default();
+        let oscillators = default_bank
+            .slots
+            .map(|slot| Oscillator::new(slot.waveform, slot.freq, slot.volume));
+
         Self {
             sample_rate: config.sample_rate as f32,
             bio_params: BioParameters::default(),
-            phase: 0.0,
+            oscillators,
+            resampler: None,
+            bio_rx,
+            capture_rx,
+            status_tx,
+            osc_rx,
+            clock_queue,
+            samples_written: 0,
+        }
+    }
+
+    /// Set up (or tear down) sample-rate conversion between the engine's
+    /// internal processing rate and `device_sample_rate`. A no-op when the
+    /// rates already match.
+    pub fn configure_resampling(&mut self, device_sample_rate: f32, buffer_size: usize) {
+        if (device_sample_rate - self.sample_rate).abs() < 0.5 {
+            self.resampler = None;
+            return;
+        }
+
+        match StreamResampler::new(self.sample_rate, device_sample_rate, buffer_size) {
+            Ok(resampler) => self.resampler = Some(resampler),
+            Err(e) => {
+                eprintln!("[AudioProcessor] Failed to set up sample-rate converter: {e}");
+                self.resampler = None;
+            }
         }
     }
 
-    /// Process audio buffer
+    /// Process audio buffer, resampling to the device's rate when one is configured
     pub fn process(&mut self, output: &mut [f32]) {
-        // Bio-reactive sine wave (440 Hz * HRV coherence)
-        let freq = 440.0 * (0.5 + self.bio_params.hrv_coherence);
-        let phase_increment = freq / self.sample_rate;
+        if self.resampler.is_none() {
+            self.process_raw(output);
+            return;
+        }
+
+        let mut written = 0usize;
+        while written < output.len() {
+            let popped = self
+                .resampler
+                .as_mut()
+                .expect("checked above")
+                .pop(&mut output[written..]);
+            written += popped;
+
+            if written < output.len() {
+                let needed = self
+                    .resampler
+                    .as_ref()
+                    .expect("checked above")
+                    .input_frames_next();
+                let mut raw = vec![0.0; needed];
+                self.process_raw(&mut raw);
+                self.resampler.as_mut().expect("checked above").push(&raw);
+            }
+        }
+    }
+
+    /// Generate audio at the engine's internal sample rate, with no resampling
+    fn process_raw(&mut self, output: &mut [f32]) {
+        // Drain the lock-free control mailboxes at the top of every buffer:
+        // host-supplied parameters first, then microphone-derived level/pitch
+        // on top so the engine stays self-driving even if a host is also
+        // calling `update_bio_parameters` with stale values.
+        if let Some(params) = self.bio_rx.recv() {
+            self.bio_params = params;
+        }
+        if let Some(captured) = self.capture_rx.recv() {
+            self.bio_params.audio_level = captured.audio_level;
+            self.bio_params.voice_pitch = captured.voice_pitch;
+        }
+        if let Some(bank) = self.osc_rx.recv() {
+            for (osc, slot) in self.oscillators.iter_mut().zip(bank.slots.iter()) {
+                osc.waveform = slot.waveform;
+                osc.freq = slot.freq;
+                osc.volume = slot.volume;
+            }
+        }
+        self.status_tx.send(CapturedAudioParams {
+            audio_level: self.bio_params.audio_level,
+            voice_pitch: self.bio_params.voice_pitch,
+        });
+
+        // Tag this buffer with the sample position at which it begins
+        // playing, so a render loop can later line up a visual frame with
+        // whatever's actually audible at that point in the stream.
+        self.clock_queue.push(self.samples_written, self.bio_params);
+        self.samples_written += output.len() as u64;
+
+        // HRV coherence modulates the whole bank's frequency; each
+        // oscillator is additionally detuned proportional to its index,
+        // spreading out as coherence drops.
+        let freq_mod = 0.5 + self.bio_params.hrv_coherence;
+        let active_count = self.oscillators.iter().filter(|o| o.volume > 0.0).count().max(1) as f32;
 
         for sample in output.iter_mut() {
-            *sample = (self.phase * 2.0 * std::f32::consts::PI).sin() * 0.2;
-            self.phase += phase_increment;
-            if self.phase >= 1.0 {
-                self.phase -= 1.0;
+            let mut mixed = 0.0;
+            for (index, osc) in self.oscillators.iter_mut().enumerate() {
+                let detune = 1.0 + index as f32 * 0.002 * (1.0 - self.bio_params.hrv_coherence);
+                mixed += osc.next_sample(self.sample_rate, freq_mod * detune);
             }
+            *sample = mixed / active_count;
         }
     }
 
     pub fn update_bio_parameters(&mut self, params: BioParameters) {
         self.bio_params = params;
     }
+
+    /// Fold a microphone analysis result into the live bio-parameters,
+    /// making the processor self-driving from captured audio.
+    pub fn update_captured(&mut self, audio_level: f32, voice_pitch: f32) {
+        self.bio_params.audio_level = audio_level;
+        self.bio_params.voice_pitch = voice_pitch;
+    }
+
+    /// Current microphone-derived parameters
+    pub fn captured_params(&self) -> CapturedAudioParams {
+        CapturedAudioParams {
+            audio_level: self.bio_params.audio_level,
+            voice_pitch: self.bio_params.voice_pitch,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -119,7 +467,18 @@ mod tests {
     #[test]
     fn test_audio_processor() {
         let config = AudioConfig::default();
-        let mut processor = AudioProcessor::new(&config);
+        let (_bio_tx, bio_rx) = control::mailbox::<BioParameters>();
+        let (_capture_tx, capture_rx) = control::mailbox::<CapturedAudioParams>();
+        let (status_tx, _status_rx) = control::mailbox::<CapturedAudioParams>();
+        let (_osc_tx, osc_rx) = control::mailbox::<OscillatorBank>();
+        let mut processor = AudioProcessor::new(
+            &config,
+            bio_rx,
+            capture_rx,
+            status_tx,
+            osc_rx,
+            ClockedQueue::new(),
+        );
 
         let mut buffer = vec![0.0; 256];
         processor.process(&mut buffer);
@@ -142,4 +501,56 @@ mod tests {
         // 256 samples @ 48kHz = ~5.33ms
         assert!((latency - 5.33).abs() < 0.1);
     }
+
+    #[test]
+    fn test_update_bio_parameters_is_lock_free() {
+        let config = AudioConfig::default();
+        let engine = AudioEngine::new(config).unwrap();
+
+        let params = BioParameters {
+            heart_rate: 90.0,
+            ..Default::default()
+        };
+        engine.update_bio_parameters(params);
+
+        assert_eq!(engine.bio_rx.recv().unwrap().heart_rate, 90.0);
+    }
+
+    #[test]
+    fn test_set_waveform_updates_oscillator_bank() {
+        let config = AudioConfig::default();
+        let mut engine = AudioEngine::new(config).unwrap();
+
+        engine.set_waveform(1, Waveform::Square, 220.0, 0.3);
+
+        let bank = engine.osc_rx.recv().unwrap();
+        assert_eq!(bank.slots[1].waveform, Waveform::Square);
+        assert_eq!(bank.slots[1].freq, 220.0);
+        assert_eq!(bank.slots[1].volume, 0.3);
+    }
+
+    #[test]
+    fn test_process_raw_tags_clock_queue() {
+        let config = AudioConfig::default();
+        let (_bio_tx, bio_rx) = control::mailbox::<BioParameters>();
+        let (_capture_tx, capture_rx) = control::mailbox::<CapturedAudioParams>();
+        let (status_tx, _status_rx) = control::mailbox::<CapturedAudioParams>();
+        let (_osc_tx, osc_rx) = control::mailbox::<OscillatorBank>();
+        let clock_queue = ClockedQueue::new();
+        let mut processor = AudioProcessor::new(
+            &config,
+            bio_rx,
+            capture_rx,
+            status_tx,
+            osc_rx,
+            clock_queue.clone(),
+        );
+
+        let mut buffer = vec![0.0; 256];
+        processor.process(&mut buffer);
+        processor.process(&mut buffer);
+
+        let (position, _) = clock_queue.pop_latest().unwrap();
+        assert_eq!(position, 256);
+    }
 }
@@ -15,14 +15,25 @@ use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use std::sync::Arc;
 
+pub mod analysis;
+pub mod clock;
+pub mod control;
 pub mod engine;
 pub mod processor;
 pub mod buffer;
 pub mod midi;
+pub mod oscillator;
+pub mod resample;
+pub mod spatial;
+pub mod synth;
 
-pub use engine::AudioEngine;
+pub use clock::ClockedQueue;
+pub use engine::{AudioEngine, DeviceInfo};
 pub use processor::AudioProcessor;
 pub use buffer::AudioBuffer;
+pub use oscillator::{Oscillator, OscillatorBank, Waveform};
+pub use spatial::{Hrir, HrirDataset, HrtfProcessor};
+pub use synth::{Algorithm, FmSynth, OperatorConfig};
 
 /// Audio configuration
 #[derive(Debug, Clone)]
@@ -68,6 +79,13 @@ pub struct BioParameters {
 
     /// Voice pitch (Hz)
     pub voice_pitch: f32,
+
+    /// Detected voice azimuth (degrees, 0 = front, +90 = right), for placing
+    /// the voice in the HRTF field via [`spatial::HrtfProcessor`]
+    pub voice_azimuth_deg: f32,
+
+    /// Detected voice elevation (degrees, 0 = ear level)
+    pub voice_elevation_deg: f32,
 }
 
 impl Default for BioParameters {
@@ -78,6 +96,8 @@ impl Default for BioParameters {
             breathing_rate: 12.0,
             audio_level: 0.5,
             voice_pitch: 0.0,
+            voice_azimuth_deg: 0.0,
+            voice_elevation_deg: 0.0,
         }
     }
 }
@@ -0,0 +1,140 @@
+//! Bio-reactive oscillator bank
+//!
+//! Each [`Oscillator`] tracks its own phase across buffer boundaries, so
+//! changing its waveform, frequency, or volume mid-stream never introduces
+//! a click beyond the waveform's own shape at that phase.
+
+use std::f32::consts::TAU;
+
+/// Maximum number of oscillators [`AudioProcessor`](crate::engine::AudioProcessor) mixes together.
+pub const MAX_OSCILLATORS: usize = 4;
+
+/// Oscillator waveform shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Triangle,
+    Sawtooth,
+}
+
+/// A single bio-reactive oscillator: phase-continuous, detunable, volume-controlled.
+#[derive(Debug, Clone, Copy)]
+pub struct Oscillator {
+    pub waveform: Waveform,
+    pub freq: f32,
+    pub volume: f32,
+    phase: f32, // 0.0..1.0, carried across process() calls
+}
+
+impl Oscillator {
+    pub fn new(waveform: Waveform, freq: f32, volume: f32) -> Self {
+        Self {
+            waveform,
+            freq,
+            volume,
+            phase: 0.0,
+        }
+    }
+
+    pub fn silent() -> Self {
+        Self::new(Waveform::Sine, 0.0, 0.0)
+    }
+
+    /// Advance by one sample at `sample_rate` and return the next sample,
+    /// scaled by `volume`. `freq_ratio` composes bio-driven frequency
+    /// modulation with any per-oscillator detune (1.0 = no change).
+    pub fn next_sample(&mut self, sample_rate: f32, freq_ratio: f32) -> f32 {
+        let value = match self.waveform {
+            Waveform::Sine => (self.phase * TAU).sin(),
+            Waveform::Square => {
+                if self.phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Triangle => 4.0 * (self.phase - 0.5).abs() - 1.0,
+            Waveform::Sawtooth => 2.0 * self.phase - 1.0,
+        };
+
+        let effective_freq = self.freq * freq_ratio;
+        self.phase += effective_freq / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+        }
+
+        value * self.volume
+    }
+}
+
+/// One oscillator's configuration, as pushed through the lock-free control
+/// path (see [`crate::control`]).
+#[derive(Debug, Clone, Copy)]
+pub struct OscillatorSlot {
+    pub waveform: Waveform,
+    pub freq: f32,
+    pub volume: f32,
+}
+
+impl OscillatorSlot {
+    pub fn silent() -> Self {
+        Self {
+            waveform: Waveform::Sine,
+            freq: 0.0,
+            volume: 0.0,
+        }
+    }
+}
+
+/// A full bank configuration, sent as one `Copy` snapshot so updating one
+/// oscillator never races with updating another.
+#[derive(Debug, Clone, Copy)]
+pub struct OscillatorBank {
+    pub slots: [OscillatorSlot; MAX_OSCILLATORS],
+}
+
+impl Default for OscillatorBank {
+    fn default() -> Self {
+        let mut slots = [OscillatorSlot::silent(); MAX_OSCILLATORS];
+        slots[0] = OscillatorSlot {
+            waveform: Waveform::Sine,
+            freq: 440.0,
+            volume: 0.2,
+        };
+        Self { slots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_is_periodic() {
+        let mut osc = Oscillator::new(Waveform::Sine, 100.0, 1.0);
+        let sample_rate = 4800.0;
+        let period_samples = (sample_rate / 100.0) as usize;
+
+        let first: Vec<f32> = (0..period_samples)
+            .map(|_| osc.next_sample(sample_rate, 1.0))
+            .collect();
+        let second: Vec<f32> = (0..period_samples)
+            .map(|_| osc.next_sample(sample_rate, 1.0))
+            .collect();
+
+        for (a, b) in first.iter().zip(second.iter()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_square_is_bipolar() {
+        let mut osc = Oscillator::new(Waveform::Square, 100.0, 1.0);
+        let sample_rate = 4800.0;
+        for _ in 0..64 {
+            let sample = osc.next_sample(sample_rate, 1.0);
+            assert!(sample == 1.0 || sample == -1.0);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Sample-rate conversion between the engine's internal processing rate and
+//! whatever rate the output device actually negotiated.
+//!
+//! Wraps a `rubato` polynomial resampler for pitch-accurate rate conversion
+//! and a `ringbuf` ring buffer to absorb the mismatch between rubato's
+//! fixed-size input chunks and cpal's arbitrarily-sized callback buffers.
+
+use anyhow::{Context, Result};
+use ringbuf::traits::{Consumer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use rubato::{FastFixedOut, PolynomialDegree, Resampler};
+
+/// Converts a single-channel stream produced at `input_rate` into
+/// `output_rate`, buffering converted samples until the device callback is
+/// ready to drain them.
+pub struct StreamResampler {
+    resampler: FastFixedOut<f32>,
+    input_buf: Vec<f32>,
+    producer: HeapProd<f32>,
+    consumer: HeapCons<f32>,
+}
+
+impl StreamResampler {
+    /// `chunk_size` is a hint for the device's callback size; rubato is free
+    /// to ask for a different number of input frames per call via
+    /// `input_frames_next`.
+    pub fn new(input_rate: f32, output_rate: f32, chunk_size: usize) -> Result<Self> {
+        let ratio = output_rate as f64 / input_rate as f64;
+        let resampler = FastFixedOut::<f32>::new(ratio, 2.0, PolynomialDegree::Cubic, chunk_size, 1)
+            .context("Failed to construct sample-rate converter")?;
+
+        // Generous enough that a handful of device callbacks can be served
+        // from already-converted samples without blocking on rubato mid-callback.
+        let ring_capacity = chunk_size * 8 + 64;
+        let ring = HeapRb::<f32>::new(ring_capacity);
+        let (producer, consumer) = ring.split();
+
+        Ok(Self {
+            resampler,
+            input_buf: Vec::with_capacity(chunk_size),
+            producer,
+            consumer,
+        })
+    }
+
+    /// Push freshly generated input-rate samples, resampling in rubato's
+    /// required chunk size and staging the result in the ring buffer.
+    pub fn push(&mut self, samples: &[f32]) {
+        self.input_buf.extend_from_slice(samples);
+
+        loop {
+            let chunk_size = self.resampler.input_frames_next();
+            if self.input_buf.len() < chunk_size {
+                break;
+            }
+
+            let chunk: Vec<f32> = self.input_buf.drain(..chunk_size).collect();
+            let input = [chunk];
+            if let Ok(converted) = self.resampler.process(&input, None) {
+                self.producer.push_slice(&converted[0]);
+            }
+        }
+    }
+
+    /// Drain already-converted samples into `output`, returning the number
+    /// of frames actually written (fewer than `output.len()` means the ring
+    /// buffer underran and the caller should `push` more input first).
+    pub fn pop(&mut self, output: &mut [f32]) -> usize {
+        self.consumer.pop_slice(output)
+    }
+
+    /// Frames of input-rate audio rubato needs before it can produce more output.
+    pub fn input_frames_next(&self) -> usize {
+        self.resampler.input_frames_next()
+    }
+}
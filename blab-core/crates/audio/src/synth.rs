@@ -0,0 +1,365 @@
+//! Multi-operator FM synthesizer
+//!
+//! Classic operator-stack FM synthesis: each voice is a small graph of sine
+//! operators wired together by an [`Algorithm`] (e.g. op1 phase-modulates
+//! op0), each operator carrying its own ratio, modulation index, and ADSR
+//! envelope. Carrier operators mix to the output. Usable standalone as a
+//! sample source, the same shape as [`crate::engine::AudioProcessor::process`].
+
+use crate::BioParameters;
+
+/// One operator's static configuration within an [`Algorithm`].
+#[derive(Debug, Clone, Copy)]
+pub struct OperatorConfig {
+    /// Frequency ratio relative to the voice's fundamental.
+    pub ratio: f32,
+    /// Modulation index: how strongly this operator's *modulators* bend its
+    /// phase (ignored for an operator with no modulators).
+    pub index: f32,
+    pub attack_s: f32,
+    pub decay_s: f32,
+    pub sustain: f32,
+    pub release_s: f32,
+}
+
+impl Default for OperatorConfig {
+    fn default() -> Self {
+        Self {
+            ratio: 1.0,
+            index: 1.0,
+            attack_s: 0.01,
+            decay_s: 0.1,
+            sustain: 0.8,
+            release_s: 0.2,
+        }
+    }
+}
+
+/// Q16.16 fixed-point "1.0".
+const FIXED_ONE: i32 = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvelopeStage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// ADSR envelope, accumulated in Q16.16 fixed point. Rates are computed and
+/// rescaled in 64-bit arithmetic before narrowing to `i32`, and per-sample
+/// increments are added/subtracted with saturating `i32` ops throughout; no
+/// bit shifts are used, so a short attack time can't overshoot into the
+/// sign bit and collapse the whole attack ramp to a single sample instead
+/// of climbing smoothly to `FIXED_ONE`.
+#[derive(Debug, Clone, Copy)]
+struct Envelope {
+    stage: EnvelopeStage,
+    level: i32,
+    /// Rates at `rate_scale == 1.0`; `note_on` rescales these into the
+    /// working `*_increment` fields below.
+    base_attack_increment: i32,
+    base_decay_increment: i32,
+    base_release_increment: i32,
+    attack_increment: i32,
+    decay_increment: i32,
+    sustain_level: i32,
+    release_increment: i32,
+}
+
+impl Envelope {
+    fn new(sample_rate: f32, config: &OperatorConfig) -> Self {
+        let rate_increment = |seconds: f32| -> i32 {
+            let samples = (seconds.max(0.0) * sample_rate).max(1.0) as i64;
+            // Compute in i64 and narrow afterwards so a very short `seconds`
+            // can't produce an i32 division result that wraps into the sign
+            // bit before we've even started ramping.
+            ((FIXED_ONE as i64) / samples) as i32
+        };
+
+        let base_attack_increment = rate_increment(config.attack_s);
+        let base_decay_increment = rate_increment(config.decay_s);
+        let base_release_increment = rate_increment(config.release_s);
+
+        Self {
+            stage: EnvelopeStage::Idle,
+            level: 0,
+            base_attack_increment,
+            base_decay_increment,
+            base_release_increment,
+            attack_increment: base_attack_increment,
+            decay_increment: base_decay_increment,
+            sustain_level: (config.sustain.clamp(0.0, 1.0) * FIXED_ONE as f32) as i32,
+            release_increment: base_release_increment,
+        }
+    }
+
+    /// Trigger the envelope, rescaling its rates by `rate_scale` (driven by
+    /// `BioParameters::heart_rate` — a racing pulse shortens attack/decay/
+    /// release). Scaling happens in f64/i64 before narrowing back to i32 for
+    /// the same reason `rate_increment` does: an overflowed scale must
+    /// saturate, not silently flip sign and run the envelope backwards.
+    fn note_on(&mut self, rate_scale: f32) {
+        let scale = |base: i32| -> i32 {
+            ((base as i64 as f64 * rate_scale as f64) as i64).clamp(1, i32::MAX as i64) as i32
+        };
+        self.attack_increment = scale(self.base_attack_increment);
+        self.decay_increment = scale(self.base_decay_increment);
+        self.release_increment = scale(self.base_release_increment);
+
+        self.stage = EnvelopeStage::Attack;
+        self.level = 0;
+    }
+
+    fn note_off(&mut self) {
+        if self.stage != EnvelopeStage::Idle {
+            self.stage = EnvelopeStage::Release;
+        }
+    }
+
+    /// Advance one sample and return the current level as 0.0..=1.0.
+    fn advance(&mut self) -> f32 {
+        match self.stage {
+            EnvelopeStage::Idle => {}
+            EnvelopeStage::Attack => {
+                self.level = self.level.saturating_add(self.attack_increment);
+                if self.level >= FIXED_ONE {
+                    self.level = FIXED_ONE;
+                    self.stage = EnvelopeStage::Decay;
+                }
+            }
+            EnvelopeStage::Decay => {
+                self.level = self.level.saturating_sub(self.decay_increment);
+                if self.level <= self.sustain_level {
+                    self.level = self.sustain_level;
+                    self.stage = EnvelopeStage::Sustain;
+                }
+            }
+            EnvelopeStage::Sustain => {}
+            EnvelopeStage::Release => {
+                self.level = self.level.saturating_sub(self.release_increment);
+                if self.level <= 0 {
+                    self.level = 0;
+                    self.stage = EnvelopeStage::Idle;
+                }
+            }
+        }
+        self.level as f32 / FIXED_ONE as f32
+    }
+
+    fn is_active(&self) -> bool {
+        self.stage != EnvelopeStage::Idle
+    }
+}
+
+struct Operator {
+    config: OperatorConfig,
+    phase: f32,
+    envelope: Envelope,
+}
+
+impl Operator {
+    fn new(config: OperatorConfig, sample_rate: f32) -> Self {
+        Self {
+            envelope: Envelope::new(sample_rate, &config),
+            config,
+            phase: 0.0,
+        }
+    }
+
+    fn note_on(&mut self, rate_scale: f32) {
+        self.phase = 0.0;
+        self.envelope.note_on(rate_scale);
+    }
+
+    fn note_off(&mut self) {
+        self.envelope.note_off();
+    }
+
+    /// Advance one sample given the summed output of this operator's
+    /// modulators, returning this operator's (enveloped) output.
+    fn advance(&mut self, f0: f32, sample_rate: f32, modulator_sum: f32) -> f32 {
+        let out = (self.phase + modulator_sum * self.config.index).sin() * self.envelope.advance();
+
+        self.phase += 2.0 * std::f32::consts::PI * self.config.ratio * f0 / sample_rate;
+        if self.phase >= 2.0 * std::f32::consts::PI {
+            self.phase -= 2.0 * std::f32::consts::PI;
+        }
+
+        out
+    }
+}
+
+/// Operator routing graph: which operators modulate which, and which
+/// operators are carriers (summed to the voice's output).
+#[derive(Debug, Clone)]
+pub struct Algorithm {
+    /// `modulators[i]` lists the operator indices that modulate operator
+    /// `i`'s phase.
+    pub modulators: Vec<Vec<usize>>,
+    /// Operators whose output is mixed to the voice's audio output.
+    pub carriers: Vec<usize>,
+    /// Processing order; every operator's modulators must appear before it.
+    pub order: Vec<usize>,
+}
+
+impl Algorithm {
+    /// op1 -> op0 (carrier). The simplest two-operator FM stack.
+    pub fn two_op_stack() -> Self {
+        Self {
+            modulators: vec![vec![1], vec![]],
+            carriers: vec![0],
+            order: vec![1, 0],
+        }
+    }
+
+    /// op3 -> op2 -> op1 -> op0 (carrier). A classic 4-op DX7-style stack.
+    pub fn four_op_stack() -> Self {
+        Self {
+            modulators: vec![vec![1], vec![2], vec![3], vec![]],
+            carriers: vec![0],
+            order: vec![3, 2, 1, 0],
+        }
+    }
+
+    fn operator_count(&self) -> usize {
+        self.modulators.len()
+    }
+}
+
+/// A single playing (or idle) note.
+struct Voice {
+    operators: Vec<Operator>,
+    algorithm: Algorithm,
+    note: u8,
+    active: bool,
+}
+
+impl Voice {
+    fn new(operator_configs: &[OperatorConfig], algorithm: Algorithm, sample_rate: f32) -> Self {
+        let operators = operator_configs
+            .iter()
+            .map(|config| Operator::new(*config, sample_rate))
+            .collect();
+        Self {
+            operators,
+            algorithm,
+            note: 0,
+            active: false,
+        }
+    }
+
+    fn note_on(&mut self, note: u8, rate_scale: f32) {
+        self.note = note;
+        self.active = true;
+        for op in &mut self.operators {
+            op.note_on(rate_scale);
+        }
+    }
+
+    fn note_off(&mut self) {
+        for op in &mut self.operators {
+            op.note_off();
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.operators.iter().all(|op| !op.envelope.is_active())
+    }
+
+    fn advance(&mut self, f0: f32, sample_rate: f32) -> f32 {
+        let mut outputs = vec![0.0f32; self.algorithm.operator_count()];
+        for &i in &self.algorithm.order {
+            let modulator_sum: f32 = self.algorithm.modulators[i].iter().map(|&m| outputs[m]).sum();
+            outputs[i] = self.operators[i].advance(f0, sample_rate, modulator_sum);
+        }
+
+        let carrier_count = self.algorithm.carriers.len().max(1) as f32;
+        self.algorithm.carriers.iter().map(|&c| outputs[c]).sum::<f32>() / carrier_count
+    }
+}
+
+fn note_to_frequency(note: u8) -> f32 {
+    440.0 * 2f32.powf((note as f32 - 69.0) / 12.0)
+}
+
+/// Polyphonic multi-operator FM synthesizer.
+pub struct FmSynth {
+    sample_rate: f32,
+    voices: Vec<Voice>,
+    /// Per-note fundamental override (e.g. from `BioParameters::voice_pitch`);
+    /// when set, every voice renders at this frequency regardless of note.
+    pitch_override_hz: Option<f32>,
+    /// Envelope rate multiplier driven by `BioParameters::heart_rate`,
+    /// applied to new voices at `note_on` time (faster heart rate -> faster
+    /// attack/decay/release, like a racing pulse).
+    rate_scale: f32,
+}
+
+impl FmSynth {
+    /// Create a synth with `voice_count` voices, each built from the same
+    /// operator configuration and algorithm.
+    pub fn new(
+        sample_rate: f32,
+        operator_configs: Vec<OperatorConfig>,
+        algorithm: Algorithm,
+        voice_count: usize,
+    ) -> Self {
+        let voices = (0..voice_count.max(1))
+            .map(|_| Voice::new(&operator_configs, algorithm.clone(), sample_rate))
+            .collect();
+
+        Self {
+            sample_rate,
+            voices,
+            pitch_override_hz: None,
+            rate_scale: 1.0,
+        }
+    }
+
+    /// Trigger a note on the first inactive voice. If every voice is already
+    /// in use, voice 0 is retaken; voice age is not tracked.
+    pub fn note_on(&mut self, note: u8, _velocity: f32) {
+        let voice_index = self
+            .voices
+            .iter()
+            .position(|v| !v.active)
+            .unwrap_or(0);
+        self.voices[voice_index].note_on(note, self.rate_scale);
+    }
+
+    /// Release every voice currently playing `note`.
+    pub fn note_off(&mut self, note: u8) {
+        for voice in self.voices.iter_mut().filter(|v| v.active && v.note == note) {
+            voice.note_off();
+        }
+    }
+
+    /// Drive carrier pitch and envelope speed from live bio-parameters:
+    /// a nonzero `voice_pitch` pins every voice's fundamental (for a
+    /// sustained biofeedback drone), and `heart_rate` scales envelope rates
+    /// applied to subsequently triggered notes.
+    pub fn update_bio_parameters(&mut self, params: BioParameters) {
+        self.pitch_override_hz = (params.voice_pitch > 0.0).then_some(params.voice_pitch);
+        self.rate_scale = (params.heart_rate / 70.0).clamp(0.25, 4.0);
+    }
+
+    /// Render `output.len()` mono samples, mixing all active voices.
+    pub fn process(&mut self, output: &mut [f32]) {
+        for sample in output.iter_mut() {
+            let mut mixed = 0.0;
+            for voice in self.voices.iter_mut().filter(|v| v.active) {
+                let f0 = self.pitch_override_hz.unwrap_or_else(|| note_to_frequency(voice.note));
+                mixed += voice.advance(f0, self.sample_rate);
+            }
+            *sample = mixed * 0.2;
+
+            for voice in self.voices.iter_mut() {
+                if voice.active && voice.is_finished() {
+                    voice.active = false;
+                }
+            }
+        }
+    }
+}
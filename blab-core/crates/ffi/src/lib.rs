@@ -2,12 +2,13 @@
 //!
 //! C-compatible API for calling Rust from Swift
 
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::ptr;
 
 // Re-export types from core modules
-use blab_audio::{AudioConfig, AudioEngine, BioParameters as AudioBioParams};
+use blab_audio::{AudioConfig, AudioEngine, BioParameters as AudioBioParams, DeviceInfo, Waveform};
+use blab_audio::engine::CapturedAudioParams;
 
 /// Opaque pointer to AudioEngine
 pub struct BlabAudioEngine {
@@ -22,6 +23,8 @@ pub struct BlabBioParameters {
     pub breathing_rate: f32,
     pub audio_level: f32,
     pub voice_pitch: f32,
+    pub voice_azimuth_deg: f32,
+    pub voice_elevation_deg: f32,
 }
 
 impl From<BlabBioParameters> for AudioBioParams {
@@ -32,6 +35,8 @@ impl From<BlabBioParameters> for AudioBioParams {
             breathing_rate: params.breathing_rate,
             audio_level: params.audio_level,
             voice_pitch: params.voice_pitch,
+            voice_azimuth_deg: params.voice_azimuth_deg,
+            voice_elevation_deg: params.voice_elevation_deg,
         }
     }
 }
@@ -89,6 +94,35 @@ pub unsafe extern "C" fn blab_audio_engine_start(engine: *mut BlabAudioEngine) -
     }
 }
 
+/// Start audio engine on a specific output device, identified by the `id`
+/// field of a `BlabDeviceInfo` returned from `blab_audio_list_devices`.
+///
+/// # Safety
+/// `engine` must be a valid pointer. `device_id` must be a valid,
+/// null-terminated UTF-8 C string.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_engine_start_with_device(
+    engine: *mut BlabAudioEngine,
+    device_id: *const c_char,
+) -> bool {
+    if engine.is_null() || device_id.is_null() {
+        return false;
+    }
+
+    let Ok(device_id) = CStr::from_ptr(device_id).to_str() else {
+        return false;
+    };
+
+    let engine = &mut *engine;
+    match engine.engine.start_with_device(device_id) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("[FFI] Failed to start audio engine on device: {}", e);
+            false
+        }
+    }
+}
+
 /// Stop audio engine
 ///
 /// # Safety
@@ -101,6 +135,76 @@ pub unsafe extern "C" fn blab_audio_engine_stop(engine: *mut BlabAudioEngine) {
     }
 }
 
+/// Start capturing the default microphone input and deriving bio-parameters
+/// (signal level, voice pitch) from it in real time.
+///
+/// # Safety
+/// `engine` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_engine_start_input(engine: *mut BlabAudioEngine) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+
+    let engine = &mut *engine;
+    match engine.engine.start_input() {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("[FFI] Failed to start audio input: {}", e);
+            false
+        }
+    }
+}
+
+/// Stop microphone capture
+///
+/// # Safety
+/// `engine` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_engine_stop_input(engine: *mut BlabAudioEngine) {
+    if !engine.is_null() {
+        let engine = &mut *engine;
+        engine.engine.stop_input();
+    }
+}
+
+/// Microphone-derived parameters (C-compatible)
+#[repr(C)]
+pub struct BlabCapturedAudioParams {
+    pub audio_level: f32,
+    pub voice_pitch: f32,
+}
+
+impl From<CapturedAudioParams> for BlabCapturedAudioParams {
+    fn from(params: CapturedAudioParams) -> Self {
+        Self {
+            audio_level: params.audio_level,
+            voice_pitch: params.voice_pitch,
+        }
+    }
+}
+
+/// Read back the `audio_level`/`voice_pitch` the engine's output processor
+/// applied to its most recent buffer (microphone-derived while input
+/// capture is running; see `AudioEngine::captured_params`)
+///
+/// # Safety
+/// `engine` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_engine_get_captured(
+    engine: *const BlabAudioEngine,
+) -> BlabCapturedAudioParams {
+    if engine.is_null() {
+        return BlabCapturedAudioParams {
+            audio_level: 0.0,
+            voice_pitch: 0.0,
+        };
+    }
+
+    let engine = &*engine;
+    engine.engine.captured_params().into()
+}
+
 /// Update bio-reactive parameters
 ///
 /// # Safety
@@ -130,6 +234,146 @@ pub unsafe extern "C" fn blab_audio_engine_get_latency_ms(engine: *const BlabAud
     engine.engine.get_latency_ms()
 }
 
+// MARK: - Oscillator Bank
+
+/// Oscillator waveform (C-compatible)
+#[repr(C)]
+pub enum BlabWaveform {
+    Sine = 0,
+    Square = 1,
+    Triangle = 2,
+    Sawtooth = 3,
+}
+
+impl From<BlabWaveform> for Waveform {
+    fn from(kind: BlabWaveform) -> Self {
+        match kind {
+            BlabWaveform::Sine => Waveform::Sine,
+            BlabWaveform::Square => Waveform::Square,
+            BlabWaveform::Triangle => Waveform::Triangle,
+            BlabWaveform::Sawtooth => Waveform::Sawtooth,
+        }
+    }
+}
+
+/// Configure one oscillator in the bio-reactive oscillator bank mixed into
+/// the engine's output.
+///
+/// # Safety
+/// `engine` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_engine_set_waveform(
+    engine: *mut BlabAudioEngine,
+    index: usize,
+    kind: BlabWaveform,
+    base_freq: f32,
+    volume: f32,
+) {
+    if engine.is_null() {
+        return;
+    }
+
+    let engine = &mut *engine;
+    engine
+        .engine
+        .set_waveform(index, kind.into(), base_freq, volume);
+}
+
+// MARK: - Device Enumeration
+
+/// Which side of the audio path to enumerate devices for
+#[repr(C)]
+pub enum BlabDeviceDirection {
+    Output = 0,
+    Input = 1,
+}
+
+/// Device descriptor (C-compatible). `name` and `id` are heap-allocated C
+/// strings owned by this entry; free the whole array with
+/// `blab_audio_free_devices`.
+#[repr(C)]
+pub struct BlabDeviceInfo {
+    pub name: *mut c_char,
+    pub id: *mut c_char,
+    pub min_channels: u16,
+    pub max_channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+}
+
+impl BlabDeviceInfo {
+    fn from_device_info(info: DeviceInfo) -> Option<Self> {
+        let name = CString::new(info.name).ok()?;
+        let id = CString::new(info.id).ok()?;
+        Some(Self {
+            name: name.into_raw(),
+            id: id.into_raw(),
+            min_channels: info.min_channels,
+            max_channels: info.max_channels,
+            min_sample_rate: info.min_sample_rate,
+            max_sample_rate: info.max_sample_rate,
+        })
+    }
+}
+
+/// List the available audio devices for `direction`.
+///
+/// Writes the array length to `out_count` and returns a pointer to the
+/// first entry (null, with `*out_count == 0`, if there are none or
+/// `out_count` is null). Free the result with `blab_audio_free_devices`.
+///
+/// # Safety
+/// `out_count` must be a valid pointer to a `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_list_devices(
+    direction: BlabDeviceDirection,
+    out_count: *mut usize,
+) -> *mut BlabDeviceInfo {
+    if out_count.is_null() {
+        return ptr::null_mut();
+    }
+
+    let devices = match direction {
+        BlabDeviceDirection::Output => AudioEngine::list_output_devices(),
+        BlabDeviceDirection::Input => AudioEngine::list_input_devices(),
+    };
+
+    let entries: Vec<BlabDeviceInfo> = devices
+        .into_iter()
+        .filter_map(BlabDeviceInfo::from_device_info)
+        .collect();
+
+    *out_count = entries.len();
+    if entries.is_empty() {
+        return ptr::null_mut();
+    }
+
+    // `into_boxed_slice` drops any excess capacity itself, so the box's
+    // length IS its allocation size - unlike `Vec::from_raw_parts`, which
+    // would need the original capacity reconstructed exactly to avoid
+    // freeing with the wrong size.
+    Box::into_raw(entries.into_boxed_slice()) as *mut BlabDeviceInfo
+}
+
+/// Free a device array returned by `blab_audio_list_devices`.
+///
+/// # Safety
+/// `devices` and `count` must be exactly what `blab_audio_list_devices`
+/// returned (or `devices` null).
+#[no_mangle]
+pub unsafe extern "C" fn blab_audio_free_devices(devices: *mut BlabDeviceInfo, count: usize) {
+    if devices.is_null() {
+        return;
+    }
+
+    let slice = std::slice::from_raw_parts_mut(devices, count);
+    let entries = Box::from_raw(slice as *mut [BlabDeviceInfo]);
+    for entry in entries.into_vec() {
+        drop(CString::from_raw(entry.name));
+        drop(CString::from_raw(entry.id));
+    }
+}
+
 // MARK: - Version Info
 
 /// Get BLAB core version
@@ -166,6 +410,8 @@ mod tests {
                 breathing_rate: 6.0,
                 audio_level: 0.5,
                 voice_pitch: 440.0,
+                voice_azimuth_deg: 0.0,
+                voice_elevation_deg: 0.0,
             };
             blab_audio_engine_update_bio(engine, bio_params);
 
@@ -15,11 +15,15 @@ use anyhow::{Context, Result};
 use wgpu::util::DeviceExt;
 
 pub mod particles;
+pub mod post_process;
+pub mod profiler;
 pub mod shaders;
 pub mod renderer;
 
 pub use particles::ParticleSystem;
-pub use renderer::{RenderPipeline, RenderPipelineConfig, CymaticsRenderer};
+pub use post_process::PostProcessChain;
+pub use profiler::{FrameTimings, GpuProfiler};
+pub use renderer::{RenderPipeline, RenderPipelineConfig, CymaticsRenderer, FractalRenderer};
 
 /// GPU backend selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -140,6 +144,8 @@ pub struct VisualEngine {
     device: wgpu::Device,
     queue: wgpu::Queue,
     particle_system: Option<ParticleSystem>,
+    render_pipeline: Option<RenderPipeline>,
+    profiler: GpuProfiler,
 }
 
 impl VisualEngine {
@@ -163,12 +169,17 @@ impl VisualEngine {
             .await
             .context("Failed to find suitable GPU adapter")?;
 
+        // Opt into GPU timestamp queries for the profiler when the adapter
+        // supports them; omitted otherwise so device creation never fails
+        // for lack of this optional feature.
+        let required_features = wgpu::Features::TIMESTAMP_QUERY & adapter.features();
+
         // Get device and queue
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("BLAB Visual Device"),
-                    required_features: wgpu::Features::empty(),
+                    required_features,
                     required_limits: wgpu::Limits::default(),
                 },
                 None,
@@ -182,6 +193,11 @@ impl VisualEngine {
         );
         println!("[VisualEngine] GPU: {}", adapter.get_info().name);
 
+        let profiler = GpuProfiler::new(&device, &queue);
+        if profiler.is_enabled() {
+            println!("[VisualEngine] GPU timestamp profiling enabled");
+        }
+
         Ok(Self {
             config,
             instance,
@@ -189,6 +205,8 @@ impl VisualEngine {
             device,
             queue,
             particle_system: None,
+            render_pipeline: None,
+            profiler,
         })
     }
 
@@ -200,32 +218,41 @@ impl VisualEngine {
         Ok(())
     }
 
-    /// Update with bio-reactive parameters
-    pub fn update(&mut self, params: BioVisualParams, delta_time: f32) {
-        if let Some(particles) = &mut self.particle_system {
-            particles.update(&self.device, &self.queue, params, delta_time);
-        }
+    /// Initialize the render pipeline for the given swapchain format
+    pub fn init_render_pipeline(&mut self, config: RenderPipelineConfig) -> Result<()> {
+        self.render_pipeline = Some(RenderPipeline::new(&self.device, config)?);
+        Ok(())
     }
 
-    /// Render frame
-    pub fn render(&self, surface: &wgpu::Surface, width: u32, height: u32) -> Result<()> {
-        // Get surface texture
+    /// Render one frame: dispatches the particle compute pass and clears the
+    /// surface, in a single `CommandEncoder` submission, then presents it.
+    /// The particles themselves are not drawn here; `render_particles`
+    /// needs a caller-supplied bind group and is not invoked by this method.
+    pub fn render(
+        &mut self,
+        surface: &wgpu::Surface,
+        params: BioVisualParams,
+        delta_time: f32,
+    ) -> Result<()> {
         let output = surface.get_current_texture()?;
         let view = output
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
-        // Create command encoder
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("Render Encoder"),
             });
 
-        // Render pass
+        if let Some(particles) = &mut self.particle_system {
+            let timestamps = self.profiler.compute_pass_timestamps("particles");
+            particles.dispatch_compute(&mut encoder, &self.queue, params, delta_time, timestamps);
+        }
+
         {
-            let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
+            let _clear_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Clear Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view: &view,
                     resolve_target: None,
@@ -244,16 +271,40 @@ impl VisualEngine {
                 occlusion_query_set: None,
             });
 
-            // Render particles here
+            // The clear pass above is separate from `RenderPipeline::render_particles`,
+            // which callers invoke with their own bind group (camera/view uniforms)
+            // once `render_pipeline` is initialized.
         }
 
-        // Submit commands
+        self.profiler.resolve(&mut encoder);
+
         self.queue.submit(std::iter::once(encoder.finish()));
         output.present();
 
         Ok(())
     }
 
+    /// Read back the GPU timings resolved by the most recently submitted
+    /// `render` call. Returns all zeros if the adapter doesn't support
+    /// `Features::TIMESTAMP_QUERY`.
+    pub async fn frame_timings(&self) -> Result<FrameTimings> {
+        self.profiler.read_timings(&self.device).await
+    }
+
+    /// The GPU profiler, for recording timestamps around custom passes
+    /// (e.g. a `PostProcessChain::run` call) via
+    /// `GpuProfiler::render_pass_timestamps("post")`.
+    pub fn profiler(&self) -> &GpuProfiler {
+        &self.profiler
+    }
+
+    /// The render pipeline, once initialized via `init_render_pipeline`, so
+    /// callers can draw `ParticleSystem::front_buffer()` with their own
+    /// camera/view bind group after `render()`'s compute dispatch.
+    pub fn render_pipeline(&self) -> Option<&RenderPipeline> {
+        self.render_pipeline.as_ref()
+    }
+
     /// Get GPU info
     pub fn gpu_info(&self) -> String {
         let info = self.adapter.get_info();
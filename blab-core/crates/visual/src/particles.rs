@@ -1,6 +1,11 @@
 //! GPU Particle System
 //!
-//! 100,000+ particles with compute shader physics
+//! 100,000+ particles, simulated entirely on the GPU: a compute pass
+//! integrates position/velocity, decays life, and recycles dead particles
+//! from the emitter, reading bio-reactive parameters so heart rate drives
+//! animation speed, breathing rate drives emission density, and HRV
+//! coherence drives hue. Nothing but the uniform block is touched from the
+//! CPU per frame.
 
 use anyhow::Result;
 use bytemuck::{Pod, Zeroable};
@@ -24,7 +29,8 @@ pub struct Particle {
     pub brightness: f32,
 }
 
-/// Particle system uniforms
+/// Particle system uniforms, also the `BioVisualParams` read by
+/// `particle_update.wgsl` to drive the simulation.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct ParticleUniforms {
@@ -46,20 +52,23 @@ pub struct ParticleUniforms {
     pub particle_count: u32,
 }
 
-/// GPU particle system
+/// GPU particle system, double-buffered so the compute pass can read last
+/// frame's state while writing this frame's without a GPU-side race.
 pub struct ParticleSystem {
-    particle_buffer: wgpu::Buffer,
+    buffers: [wgpu::Buffer; 2],
+    bind_groups: [wgpu::BindGroup; 2],
     uniform_buffer: wgpu::Buffer,
     compute_pipeline: wgpu::ComputePipeline,
-    bind_group: wgpu::BindGroup,
     particle_count: u32,
     time: f32,
+    /// Index into `buffers`/`bind_groups` holding the buffer most recently
+    /// written by the compute pass (i.e. the one to render from).
+    front: usize,
 }
 
 impl ParticleSystem {
     /// Create new particle system
     pub fn new(device: &wgpu::Device, count: u32) -> Result<Self> {
-        // Initialize particles
         let particles = vec![
             Particle {
                 position: [0.0, 0.0],
@@ -72,14 +81,17 @@ impl ParticleSystem {
             count as usize
         ];
 
-        // Create particle buffer
-        let particle_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Particle Buffer"),
-            contents: bytemuck::cast_slice(&particles),
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-        });
+        let make_buffer = |label: &str| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents: bytemuck::cast_slice(&particles),
+                usage: wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::VERTEX
+                    | wgpu::BufferUsages::COPY_DST,
+            })
+        };
+        let buffers = [make_buffer("Particle Buffer A"), make_buffer("Particle Buffer B")];
 
-        // Create uniform buffer
         let uniforms = ParticleUniforms {
             time: 0.0,
             delta_time: 0.016,
@@ -97,13 +109,11 @@ impl ParticleSystem {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
-        // Create compute shader (placeholder)
         let compute_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Particle Compute Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/particles.wgsl").into()),
+            label: Some("Particle Update Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/particle_update.wgsl").into()),
         });
 
-        // Create compute pipeline
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Particle Bind Group Layout"),
             entries: &[
@@ -111,7 +121,7 @@ impl ParticleSystem {
                     binding: 0,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
                         has_dynamic_offset: false,
                         min_binding_size: None,
                     },
@@ -120,6 +130,16 @@ impl ParticleSystem {
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
                     visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -143,43 +163,63 @@ impl ParticleSystem {
             entry_point: "main",
         });
 
-        // Create bind group
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Particle Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: particle_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: uniform_buffer.as_entire_binding(),
-                },
-            ],
-        });
+        // bind_groups[0] reads buffers[0] and writes buffers[1] (the
+        // direction used when `front == 0`); bind_groups[1] is the reverse.
+        let make_bind_group = |label: &str, src: &wgpu::Buffer, dst: &wgpu::Buffer| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: src.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: dst.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let bind_groups = [
+            make_bind_group("Particle Bind Group A->B", &buffers[0], &buffers[1]),
+            make_bind_group("Particle Bind Group B->A", &buffers[1], &buffers[0]),
+        ];
 
         Ok(Self {
-            particle_buffer,
+            buffers,
+            bind_groups,
             uniform_buffer,
             compute_pipeline,
-            bind_group,
             particle_count: count,
             time: 0.0,
+            // Both buffers start with the same initial state; starting
+            // with index 1 as the front, the first dispatch reads buffers[1]
+            // and writes buffers[0], making index 0 the front.
+            front: 1,
         })
     }
 
-    /// Update particles
-    pub fn update(
+    /// Record the compute dispatch that integrates one frame of particle
+    /// state into the provided encoder. Does not submit; callers chain this
+    /// with a render pass in the same `CommandEncoder` so compute -> render
+    /// happens in one submission. `timestamp_writes` comes from
+    /// `GpuProfiler::compute_pass_timestamps("particles")`; pass `None` to
+    /// skip GPU timing.
+    pub fn dispatch_compute(
         &mut self,
-        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
         queue: &wgpu::Queue,
         params: super::BioVisualParams,
         delta_time: f32,
+        timestamp_writes: Option<wgpu::ComputePassTimestampWrites>,
     ) {
         self.time += delta_time;
 
-        // Update uniforms
         let uniforms = ParticleUniforms {
             time: self.time,
             delta_time,
@@ -190,28 +230,34 @@ impl ParticleSystem {
             breathing_rate: params.breathing_rate,
             particle_count: self.particle_count,
         };
-
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-        // Run compute shader
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Particle Update Encoder"),
-        });
-
+        // The bind group at `front` reads the current front buffer and
+        // writes the other one, which becomes the new front.
+        let dispatch_index = self.front;
         {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Particle Compute Pass"),
-                timestamp_writes: None,
+                timestamp_writes,
             });
-
             compute_pass.set_pipeline(&self.compute_pipeline);
-            compute_pass.set_bind_group(0, &self.bind_group, &[]);
+            compute_pass.set_bind_group(0, &self.bind_groups[dispatch_index], &[]);
 
-            // Dispatch compute shader (64 threads per workgroup)
             let workgroup_count = (self.particle_count + 63) / 64;
             compute_pass.dispatch_workgroups(workgroup_count, 1, 1);
         }
 
-        queue.submit(std::iter::once(encoder.finish()));
+        self.front = 1 - dispatch_index;
+    }
+
+    /// Storage/vertex buffer currently holding the most recently simulated
+    /// particle state, for `RenderPipeline::render_particles` to draw from.
+    pub fn front_buffer(&self) -> &wgpu::Buffer {
+        &self.buffers[self.front]
+    }
+
+    /// Total particle count.
+    pub fn particle_count(&self) -> u32 {
+        self.particle_count
     }
 }
@@ -0,0 +1,469 @@
+//! Post-processing shader chain
+//!
+//! Runs a preset-driven sequence of full-screen WGSL fragment passes after
+//! `RenderPipeline::render_particles` / `CymaticsRenderer::render` have
+//! written the scene. Each pass reads the previous pass's output as a sampled
+//! texture and renders into its own offscreen target; the final pass
+//! writes directly to the swapchain view. This lets a preset stack effects
+//! like bloom, chromatic aberration, CRT/scanlines, and feedback trails
+//! without recompiling the crate.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use wgpu::util::DeviceExt;
+
+/// One pass parsed from a `.postfx` preset file.
+#[derive(Debug, Clone)]
+struct PassDesc {
+    name: String,
+    shader_file: String,
+    /// Output resolution relative to the swapchain (e.g. 0.5 for a half-res
+    /// bloom threshold pass).
+    scale: f32,
+    /// Whether this pass reads its own previous-frame output for temporal
+    /// feedback (particle trails, motion blur).
+    history: bool,
+}
+
+fn parse_preset(preset_path: &Path) -> Result<Vec<PassDesc>> {
+    let contents = fs::read_to_string(preset_path)
+        .with_context(|| format!("Failed to read postfx preset {:?}", preset_path))?;
+
+    let mut passes = Vec::new();
+    for (lineno, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            bail!(
+                "{:?}:{}: expected `name shader scale [history]`, got {:?}",
+                preset_path,
+                lineno + 1,
+                line
+            );
+        }
+
+        passes.push(PassDesc {
+            name: fields[0].to_string(),
+            shader_file: fields[1].to_string(),
+            scale: fields[2]
+                .parse()
+                .with_context(|| format!("{:?}:{}: invalid scale", preset_path, lineno + 1))?,
+            history: fields.get(3) == Some(&"history"),
+        });
+    }
+
+    if passes.is_empty() {
+        bail!("{:?}: preset defines no passes", preset_path);
+    }
+
+    Ok(passes)
+}
+
+/// Uniforms fed automatically to every pass before it runs.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PassUniforms {
+    output_resolution: [f32; 2],
+    source_resolution: [f32; 2],
+    frame: u32,
+    time: f32,
+    scale: f32,
+    _padding: f32,
+}
+
+/// An offscreen render target owned by the chain (a ping-pong slot or a
+/// pass's history buffer).
+struct Target {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    width: u32,
+    height: u32,
+}
+
+impl Target {
+    fn new(device: &wgpu::Device, label: &str, width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            // COPY_SRC/COPY_DST: `run` copies a pass's target into its history slot.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            width,
+            height,
+        }
+    }
+}
+
+struct Pass {
+    desc: PassDesc,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    /// This pass's own offscreen output, for every pass but the last, which
+    /// targets the swapchain view directly.
+    target: Option<Target>,
+    /// Previous frame's output, present only when `desc.history` is set.
+    history: Option<Target>,
+}
+
+/// A loaded, ready-to-run chain of post-processing passes.
+pub struct PostProcessChain {
+    passes: Vec<Pass>,
+    shader_dir: PathBuf,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    frame: u32,
+    /// Swapchain size passed to the most recent `resize` call, reused by
+    /// `reload` so it doesn't have to probe pass targets for it.
+    last_size: Option<(u32, u32)>,
+}
+
+impl PostProcessChain {
+    /// Load a chain from a `.postfx` preset file. `shader_dir` is the
+    /// directory WGSL pass shaders are resolved relative to (normally
+    /// `src/shaders/post`).
+    pub fn load_preset(
+        device: &wgpu::Device,
+        preset_path: impl AsRef<Path>,
+        shader_dir: impl Into<PathBuf>,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Result<Self> {
+        let descs = parse_preset(preset_path.as_ref())?;
+        let shader_dir = shader_dir.into();
+
+        let passes = descs
+            .into_iter()
+            .map(|desc| Self::build_pass(device, &shader_dir, desc, format))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            passes,
+            shader_dir,
+            format,
+            sample_count,
+            frame: 0,
+            last_size: None,
+        })
+    }
+
+    fn build_pass(
+        device: &wgpu::Device,
+        shader_dir: &Path,
+        desc: PassDesc,
+        format: wgpu::TextureFormat,
+    ) -> Result<Pass> {
+        let shader_path = shader_dir.join(&desc.shader_file);
+        let source = fs::read_to_string(&shader_path)
+            .with_context(|| format!("Failed to read post-process shader {:?}", shader_path))?;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&format!("PostFx Shader: {}", desc.name)),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        let mut entries = vec![
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ];
+        if desc.history {
+            entries.push(wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            });
+        }
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&format!("PostFx Bind Group Layout: {}", desc.name)),
+            entries: &entries,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&format!("PostFx Pipeline Layout: {}", desc.name)),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!("PostFx Pipeline: {}", desc.name)),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("PostFx Uniforms: {}", desc.name)),
+            contents: bytemuck::bytes_of(&PassUniforms::zeroed()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(&format!("PostFx Sampler: {}", desc.name)),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Ok(Pass {
+            desc,
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            sampler,
+            target: None,
+            history: None,
+        })
+    }
+
+    /// (Re)allocate intermediate textures for the current swapchain size.
+    /// Must be called whenever the swapchain resizes, and once before the
+    /// first `run`.
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let last = self.passes.len() - 1;
+        for (i, pass) in self.passes.iter_mut().enumerate() {
+            let w = ((width as f32) * pass.desc.scale).round() as u32;
+            let h = ((height as f32) * pass.desc.scale).round() as u32;
+
+            pass.target = if i == last {
+                None // final pass writes straight to the swapchain view
+            } else {
+                Some(Target::new(device, &format!("PostFx Target: {}", pass.desc.name), w, h, self.format))
+            };
+
+            pass.history = if pass.desc.history {
+                Some(Target::new(device, &format!("PostFx History: {}", pass.desc.name), w, h, self.format))
+            } else {
+                None
+            };
+        }
+
+        self.last_size = Some((width, height));
+    }
+
+    /// Reload the chain from disk in place, preserving allocated targets by
+    /// re-running `resize` with the last known size. Useful for live-editing
+    /// presets during development.
+    pub fn reload(&mut self, device: &wgpu::Device, preset_path: impl AsRef<Path>) -> Result<()> {
+        let (width, height) = self.last_size.unwrap_or((0, 0));
+
+        let descs = parse_preset(preset_path.as_ref())?;
+        self.passes = descs
+            .into_iter()
+            .map(|desc| Self::build_pass(device, &self.shader_dir, desc, self.format))
+            .collect::<Result<Vec<_>>>()?;
+
+        if width > 0 && height > 0 {
+            self.resize(device, width, height);
+        }
+        Ok(())
+    }
+
+    /// Run every pass, reading `input` as pass 0's source and writing the
+    /// final pass to `swapchain_view`. `time` is elapsed seconds.
+    /// `timestamp_writes` comes from
+    /// `GpuProfiler::render_pass_timestamps("post")` and is attached to the
+    /// first pass's begin timestamp and the last pass's end timestamp, so it
+    /// covers the whole chain; pass `None` to skip GPU timing.
+    pub fn run(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        input: &wgpu::TextureView,
+        swapchain_view: &wgpu::TextureView,
+        swapchain_width: u32,
+        swapchain_height: u32,
+        time: f32,
+        timestamp_writes: Option<wgpu::RenderPassTimestampWrites>,
+    ) {
+        self.frame = self.frame.wrapping_add(1);
+
+        let mut source = input;
+        let mut source_size = (swapchain_width, swapchain_height);
+
+        let last_index = self.passes.len() - 1;
+        for (index, pass) in self.passes.iter_mut().enumerate() {
+            let (out_view, out_size) = match &pass.target {
+                Some(target) => (&target.view, (target.width, target.height)),
+                None => (swapchain_view, (swapchain_width, swapchain_height)),
+            };
+
+            let uniforms = PassUniforms {
+                output_resolution: [out_size.0 as f32, out_size.1 as f32],
+                source_resolution: [source_size.0 as f32, source_size.1 as f32],
+                frame: self.frame,
+                time,
+                scale: pass.desc.scale,
+                _padding: 0.0,
+            };
+            queue.write_buffer(&pass.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+            let mut entries = vec![
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: pass.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&pass.sampler),
+                },
+            ];
+            if let Some(history) = &pass.history {
+                entries.push(wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&history.view),
+                });
+            }
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(&format!("PostFx Bind Group: {}", pass.desc.name)),
+                layout: &pass.bind_group_layout,
+                entries: &entries,
+            });
+
+            let pass_timestamp_writes = timestamp_writes.as_ref().and_then(|writes| {
+                let beginning = (index == 0).then_some(writes.beginning_of_pass_write_index).flatten();
+                let end = (index == last_index).then_some(writes.end_of_pass_write_index).flatten();
+                (beginning.is_some() || end.is_some()).then_some(wgpu::RenderPassTimestampWrites {
+                    query_set: writes.query_set,
+                    beginning_of_pass_write_index: beginning,
+                    end_of_pass_write_index: end,
+                })
+            });
+
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some(&format!("PostFx Pass: {}", pass.desc.name)),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: out_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: pass_timestamp_writes,
+                    occlusion_query_set: None,
+                });
+                render_pass.set_pipeline(&pass.pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+
+            // Copy this frame's output into the history slot so next frame's
+            // pass can read its own previous output for temporal feedback.
+            if let (Some(target), Some(history)) = (&pass.target, &pass.history) {
+                encoder.copy_texture_to_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &target.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::ImageCopyTexture {
+                        texture: &history.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::Extent3d {
+                        width: target.width,
+                        height: target.height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+
+            source = out_view;
+            source_size = out_size;
+        }
+    }
+
+    /// Number of passes in the chain.
+    pub fn pass_count(&self) -> usize {
+        self.passes.len()
+    }
+}
+
+impl PassUniforms {
+    fn zeroed() -> Self {
+        bytemuck::Zeroable::zeroed()
+    }
+}
@@ -0,0 +1,177 @@
+//! GPU timestamp-query profiler
+//!
+//! Opt-in, per-subsystem GPU timing: when the adapter supports
+//! `Features::TIMESTAMP_QUERY`, each tracked pass writes a begin/end
+//! timestamp and `GpuProfiler::read_timings` resolves them into a
+//! [`FrameTimings`] breakdown so apps can display per-subsystem GPU cost. On
+//! adapters without the feature, every method is a harmless no-op and
+//! `read_timings` returns all zeros.
+
+use anyhow::Result;
+
+/// Tracked subsystems, in the order their timestamp pairs are laid out in
+/// the query set.
+const SLOTS: &[&str] = &["particles", "cymatics", "post"];
+
+/// Per-subsystem GPU frame time breakdown, in milliseconds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameTimings {
+    pub particles_ms: f32,
+    pub cymatics_ms: f32,
+    pub post_ms: f32,
+    pub total_ms: f32,
+}
+
+/// GPU pass profiler. Construct once per `VisualEngine` and reuse every
+/// frame; the query set is rewritten in place by each frame's tracked
+/// passes, so there's nothing to reset between frames.
+pub struct GpuProfiler {
+    enabled: bool,
+    query_set: Option<wgpu::QuerySet>,
+    resolve_buffer: Option<wgpu::Buffer>,
+    readback_buffer: Option<wgpu::Buffer>,
+    period_ns: f32,
+}
+
+impl GpuProfiler {
+    /// Create a profiler, enabling real GPU timing only if `device` was
+    /// created with `Features::TIMESTAMP_QUERY`.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let enabled = device.features().contains(wgpu::Features::TIMESTAMP_QUERY);
+        if !enabled {
+            return Self {
+                enabled: false,
+                query_set: None,
+                resolve_buffer: None,
+                readback_buffer: None,
+                period_ns: 1.0,
+            };
+        }
+
+        let query_count = (SLOTS.len() * 2) as u32;
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("GPU Profiler Query Set"),
+            ty: wgpu::QueryType::Timestamp,
+            count: query_count,
+        });
+
+        let buffer_size = (query_count as u64) * std::mem::size_of::<u64>() as u64;
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Profiler Resolve Buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Profiler Readback Buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            enabled: true,
+            query_set: Some(query_set),
+            resolve_buffer: Some(resolve_buffer),
+            readback_buffer: Some(readback_buffer),
+            period_ns: queue.get_timestamp_period(),
+        }
+    }
+
+    fn slot_index(name: &str) -> Option<usize> {
+        SLOTS.iter().position(|&s| s == name)
+    }
+
+    /// Timestamp writes for a render pass tracking `slot` (one of
+    /// `"particles"`, `"cymatics"`, `"post"`). Returns `None` when
+    /// profiling is disabled or the name is unrecognized, so callers can do
+    /// `timestamp_writes: profiler.render_pass_timestamps("particles")`
+    /// unconditionally.
+    pub fn render_pass_timestamps(&self, slot: &str) -> Option<wgpu::RenderPassTimestampWrites> {
+        let query_set = self.query_set.as_ref()?;
+        let index = Self::slot_index(slot)?;
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some((index * 2) as u32),
+            end_of_pass_write_index: Some((index * 2 + 1) as u32),
+        })
+    }
+
+    /// Timestamp writes for a compute pass tracking `slot`. See
+    /// `render_pass_timestamps`.
+    pub fn compute_pass_timestamps(&self, slot: &str) -> Option<wgpu::ComputePassTimestampWrites> {
+        let query_set = self.query_set.as_ref()?;
+        let index = Self::slot_index(slot)?;
+        Some(wgpu::ComputePassTimestampWrites {
+            query_set,
+            beginning_of_pass_write_index: Some((index * 2) as u32),
+            end_of_pass_write_index: Some((index * 2 + 1) as u32),
+        })
+    }
+
+    /// Resolve this frame's queries and schedule the readback copy. Call
+    /// once per frame after all tracked passes have been recorded, before
+    /// submitting the encoder.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        let (Some(query_set), Some(resolve_buffer), Some(readback_buffer)) =
+            (&self.query_set, &self.resolve_buffer, &self.readback_buffer)
+        else {
+            return;
+        };
+
+        let query_count = (SLOTS.len() * 2) as u32;
+        encoder.resolve_query_set(query_set, 0..query_count, resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(resolve_buffer, 0, readback_buffer, 0, resolve_buffer.size());
+        let _ = readback_buffer;
+    }
+
+    /// Map the readback buffer and convert this frame's timestamp deltas
+    /// into a [`FrameTimings`] breakdown. Must be called after the
+    /// `CommandEncoder` from the matching `resolve` call has been submitted.
+    pub async fn read_timings(&self, device: &wgpu::Device) -> Result<FrameTimings> {
+        let Some(readback_buffer) = &self.readback_buffer else {
+            return Ok(FrameTimings::default());
+        };
+        if !self.enabled {
+            return Ok(FrameTimings::default());
+        }
+
+        let slice = readback_buffer.slice(..);
+        let (tx, rx) = futures_channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        rx.await??;
+
+        let data = slice.get_mapped_range();
+        let timestamps: &[u64] = bytemuck::cast_slice(&data);
+
+        let ms_per_tick = self.period_ns / 1_000_000.0;
+        let delta_ms = |slot: usize| -> f32 {
+            let begin = timestamps[slot * 2];
+            let end = timestamps[slot * 2 + 1];
+            (end.saturating_sub(begin)) as f32 * ms_per_tick
+        };
+
+        let particles_ms = delta_ms(Self::slot_index("particles").unwrap());
+        let cymatics_ms = delta_ms(Self::slot_index("cymatics").unwrap());
+        let post_ms = delta_ms(Self::slot_index("post").unwrap());
+
+        drop(data);
+        readback_buffer.unmap();
+
+        Ok(FrameTimings {
+            particles_ms,
+            cymatics_ms,
+            post_ms,
+            total_ms: particles_ms + cymatics_ms + post_ms,
+        })
+    }
+
+    /// Whether the adapter supports real GPU timing (vs. this profiler
+    /// being a no-op).
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
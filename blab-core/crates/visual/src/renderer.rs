@@ -144,7 +144,9 @@ impl RenderPipeline {
         })
     }
 
-    /// Render particles to texture
+    /// Render particles to texture. `timestamp_writes` comes from
+    /// `GpuProfiler::render_pass_timestamps("particles")`; pass `None` to
+    /// skip GPU timing.
     pub fn render_particles(
         &self,
         encoder: &mut wgpu::CommandEncoder,
@@ -152,6 +154,7 @@ impl RenderPipeline {
         particle_buffer: &wgpu::Buffer,
         particle_count: u32,
         bind_group: &wgpu::BindGroup,
+        timestamp_writes: Option<wgpu::RenderPassTimestampWrites>,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Particle Render Pass"),
@@ -169,7 +172,7 @@ impl RenderPipeline {
                 },
             })],
             depth_stencil_attachment: None,
-            timestamp_writes: None,
+            timestamp_writes,
             occlusion_query_set: None,
         });
 
@@ -279,23 +282,63 @@ impl CymaticsRenderer {
         })
     }
 
-    /// Update frequency data
+    /// Drive the pattern from a single frequency/amplitude pair, as a single
+    /// sine plate mode (1,2). Kept for simple single-tone callers; prefer
+    /// `update_spectrum` to reproduce genuine multi-mode Chladni figures.
     pub fn update_frequency(&self, queue: &wgpu::Queue, frequency: f32, amplitude: f32) {
+        let mut amplitudes = [0.0f32; MODE_COUNT];
+        // Mode (1,1) (index 0) is degenerate: `cos(nπx)cos(mπy) -
+        // cos(mπx)cos(nπy)` (see cymatics.wgsl) is identically zero whenever
+        // m == n, so a single tone is routed to the non-degenerate (1,2)
+        // mode (index 1) instead.
+        amplitudes[1] = amplitude;
+
         let uniforms = CymaticsUniforms {
-            frequency,
-            amplitude,
+            amplitudes: pack_amplitudes(&amplitudes),
+            base_frequency: frequency,
             time: 0.0, // Updated per frame
+            grid_size: MODE_GRID_SIZE as u32,
+            _padding: 0.0,
+        };
+
+        queue.write_buffer(&self.frequency_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    /// Bin an audio FFT magnitude spectrum into the (m, n) mode grid and
+    /// upload it, so the nodal pattern morphs with the live spectrum. Modes
+    /// are ordered by ascending resonant frequency (m^2 + n^2), so low FFT
+    /// bins drive the fundamental modes and high bins drive higher-order
+    /// ones.
+    pub fn update_spectrum(&self, queue: &wgpu::Queue, spectrum: &[f32], base_frequency: f32, time: f32) {
+        let mut amplitudes = [0.0f32; MODE_COUNT];
+        if !spectrum.is_empty() {
+            let modes = mode_order();
+            let mode_count = modes.len();
+            for (index, mode) in modes.into_iter().enumerate() {
+                let bin = index * spectrum.len() / mode_count;
+                amplitudes[mode] = spectrum[bin.min(spectrum.len() - 1)];
+            }
+        }
+
+        let uniforms = CymaticsUniforms {
+            amplitudes: pack_amplitudes(&amplitudes),
+            base_frequency,
+            time,
+            grid_size: MODE_GRID_SIZE as u32,
             _padding: 0.0,
         };
 
         queue.write_buffer(&self.frequency_buffer, 0, bytemuck::bytes_of(&uniforms));
     }
 
-    /// Render cymatics pattern
+    /// Render cymatics pattern. `timestamp_writes` comes from
+    /// `GpuProfiler::render_pass_timestamps("cymatics")`; pass `None` to
+    /// skip GPU timing.
     pub fn render(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
+        timestamp_writes: Option<wgpu::RenderPassTimestampWrites>,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Cymatics Render Pass"),
@@ -308,25 +351,220 @@ impl CymaticsRenderer {
                 },
             })],
             depth_stencil_attachment: None,
-            timestamp_writes: None,
+            timestamp_writes,
             occlusion_query_set: None,
         });
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
-        render_pass.draw(0..6, 0..1); // Full-screen quad
+        render_pass.draw(0..3, 0..1); // Full-screen triangle (see cymatics.wgsl vs_main)
     }
 }
 
+/// Side length of the (m, n) Chladni mode grid; must match `GRID_SIZE` in
+/// `cymatics.wgsl`.
+const MODE_GRID_SIZE: usize = 8;
+const MODE_COUNT: usize = MODE_GRID_SIZE * MODE_GRID_SIZE;
+
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct CymaticsUniforms {
-    frequency: f32,
-    amplitude: f32,
+    /// 64 mode amplitudes packed 4-per-vec4 to match WGSL's uniform array stride.
+    amplitudes: [[f32; 4]; MODE_COUNT / 4],
+    base_frequency: f32,
     time: f32,
+    grid_size: u32,
     _padding: f32,
 }
 
+fn pack_amplitudes(amplitudes: &[f32; MODE_COUNT]) -> [[f32; 4]; MODE_COUNT / 4] {
+    let mut packed = [[0.0f32; 4]; MODE_COUNT / 4];
+    for (i, chunk) in amplitudes.chunks(4).enumerate() {
+        packed[i].copy_from_slice(chunk);
+    }
+    packed
+}
+
+/// Mode indices `(m - 1) * MODE_GRID_SIZE + (n - 1)` ordered by ascending
+/// `m^2 + n^2`, i.e. ascending resonant frequency, so spectrum bin 0 maps to
+/// the (non-degenerate) fundamental mode. Diagonal `m == n` modes are
+/// excluded: the plate displacement `cos(nπx)cos(mπy) - cos(mπx)cos(nπy)`
+/// (see cymatics.wgsl) is identically zero whenever m == n, so binning a
+/// spectrum peak there would render nothing.
+fn mode_order() -> Vec<usize> {
+    let mut modes: Vec<(usize, u32)> = (0..MODE_COUNT)
+        .filter_map(|index| {
+            let m = (index / MODE_GRID_SIZE) + 1;
+            let n = (index % MODE_GRID_SIZE) + 1;
+            (m != n).then(|| (index, (m * m + n * n) as u32))
+        })
+        .collect();
+    modes.sort_by_key(|&(_, order)| order);
+    modes.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Mandelbrot/Julia escape-time fractal renderer, bio-reactive and meant to
+/// blend over the particle/cymatics output.
+pub struct FractalRenderer {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    zoom: f32,
+    auto_zoom: bool,
+}
+
+impl FractalRenderer {
+    /// Create a new fractal renderer.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Result<Self> {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Fractal Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/fractal.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Fractal Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Fractal Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Fractal Render Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Fractal Uniform Buffer"),
+            size: std::mem::size_of::<FractalUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fractal Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        Ok(Self {
+            pipeline,
+            bind_group,
+            uniform_buffer,
+            zoom: 1.0,
+            auto_zoom: false,
+        })
+    }
+
+    /// Enable/disable auto-zoom, which animates `zoom` exponentially with
+    /// elapsed `time` in `update` instead of holding it fixed.
+    pub fn set_auto_zoom(&mut self, enabled: bool) {
+        self.auto_zoom = enabled;
+    }
+
+    /// Upload this frame's uniforms, mapping bio-reactive parameters onto
+    /// the fractal: `frequency` orbits the Julia seed around a circle and
+    /// `hrv_coherence` rotates the palette phase.
+    pub fn update(&mut self, queue: &wgpu::Queue, params: crate::BioVisualParams, time: f32) {
+        if self.auto_zoom {
+            self.zoom = (time * 0.1).exp();
+        }
+
+        let orbit_radius = 0.7885;
+        let orbit_angle = params.frequency * 0.01 + time * 0.05;
+        let julia_seed = [
+            orbit_radius * orbit_angle.cos(),
+            orbit_radius * orbit_angle.sin(),
+        ];
+
+        let uniforms = FractalUniforms {
+            center: [-0.5, 0.0],
+            zoom: self.zoom,
+            max_iterations: 200,
+            julia_seed,
+            julia_mix: 1.0,
+            palette_phase: params.hrv_coherence,
+            _padding: [0.0; 2],
+        };
+
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    /// Render the fractal. Uses `LoadOp::Load`, the same pattern
+    /// `CymaticsRenderer::render` uses, so it composes over whatever the
+    /// particle/cymatics passes already wrote to `view`.
+    pub fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        timestamp_writes: Option<wgpu::RenderPassTimestampWrites>,
+    ) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Fractal Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct FractalUniforms {
+    center: [f32; 2],
+    zoom: f32,
+    max_iterations: u32,
+    julia_seed: [f32; 2],
+    julia_mix: f32,
+    palette_phase: f32,
+    _padding: [f32; 2],
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;